@@ -1,14 +1,20 @@
 extern crate alloc;
 
 use std::{
+    collections::BTreeMap,
     fs, io,
     path::{Path, PathBuf},
-    sync::Arc,
+    process::ExitCode,
 };
 
 use clap::Parser;
-use masmlint::{self, LintSelector, Linter};
-use miden_assembly::{SourceFile, SourceId};
+use masmlint::{
+    self, Config, LintError, LintSelector, Linter, LinterError, Severity, fix, registered_lints,
+    report::Finding, sarif::SarifLog,
+};
+use miden_assembly::{
+    DefaultSourceManager, LibraryNamespace, LibraryPath, SourceId, SourceManager,
+};
 use miette::Report;
 
 /// A linter for Miden Assembly.
@@ -20,33 +26,65 @@ struct Args {
     path: String,
 
     /// Comma-separated list of lint names to exclude. These will be excluded from the default list
-    /// of lints.
+    /// of lints. Takes precedence over the lint levels in the config file.
     #[arg(short, long, value_delimiter = ',', use_value_delimiter = true)]
     exclude: Vec<String>,
 
     /// Comma-separated list of lint names to run. This list is exhaustive; no other lints will be
-    /// run.
+    /// run. Takes precedence over the lint levels in the config file.
     #[arg(short, long, value_delimiter = ',', use_value_delimiter = true)]
     select: Vec<String>,
+
+    /// Automatically fix findings that have a suggested replacement by rewriting the files in
+    /// place. Findings that cannot be fixed are reported as usual.
+    #[arg(long)]
+    fix: bool,
+
+    /// Exit with a failure code if any warnings are reported. By default, only errors cause a
+    /// failure.
+    #[arg(long)]
+    deny_warnings: bool,
+
+    /// The path of the library that the linted directory contains, e.g. `miden::kernels::tx`. The
+    /// module path of each file is derived from it, which is used to resolve imports between the
+    /// modules of the library. Use `$kernel` to lint the modules of a kernel.
+    #[arg(long)]
+    namespace: Option<String>,
+
+    /// The format in which findings are reported.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+    format: OutputFormat,
 }
 
-fn main() -> miette::Result<()> {
+#[derive(clap::ValueEnum, Debug, Clone, Copy)]
+enum OutputFormat {
+    Human,
+    Json,
+    Sarif,
+}
+
+fn main() -> miette::Result<ExitCode> {
     let args = Args::parse();
     let source_path = args.path;
     let source_path = Path::new(&source_path)
         .canonicalize()
         .map_err(|err| Report::msg(format!("{err}")))?;
 
-    let masm_files = if source_path.is_dir() {
-        get_masm_files(source_path.as_path()).map_err(|err| {
+    let config = Config::discover(&source_path)?.unwrap_or_default();
+
+    let (mut masm_files, base_path) = if source_path.is_dir() {
+        let masm_files = get_masm_files(source_path.as_path()).map_err(|err| {
             Report::msg(format!(
                 "failed to get masm files from directory {}: {err}",
                 source_path.display()
             ))
-        })?
+        })?;
+        (masm_files, source_path.as_path())
     } else {
-        vec![source_path.to_owned()]
+        let base_path = source_path.parent().unwrap_or(source_path.as_path());
+        (vec![source_path.to_owned()], base_path)
     };
+    masm_files.retain(|file| config.is_included(file));
 
     if !args.exclude.is_empty() && !args.select.is_empty() {
         return Err(Report::msg("cannot use include and exclude argument at the same time"));
@@ -60,28 +98,153 @@ fn main() -> miette::Result<()> {
         LintSelector::default()
     };
 
-    let lints = selector.select()?;
+    let namespace = args.namespace.as_deref().map(parse_namespace).transpose()?;
+    let linted = LintedFiles {
+        masm_files: &masm_files,
+        base_path,
+        config: &config,
+        selector: &selector,
+        namespace: namespace.as_ref(),
+    };
+
+    let (errors, paths) = linted.lint()?;
+    let errors = if args.fix {
+        match fix_files(errors, &paths)? {
+            (0, unfixed) => unfixed,
+            // The spans of the remaining findings refer to the source before the fixes, so the
+            // files are linted again to report them at their current location.
+            _ => linted.lint()?.0,
+        }
+    } else {
+        errors
+    };
+
+    report_errors(errors, args.format, args.deny_warnings)
+}
+
+/// The files to lint and the settings to lint them with.
+struct LintedFiles<'a> {
+    masm_files: &'a [PathBuf],
+    base_path: &'a Path,
+    config: &'a Config,
+    selector: &'a LintSelector,
+    namespace: Option<&'a LibraryPath>,
+}
+
+impl LintedFiles<'_> {
+    /// Lints the files and returns the findings and the path of each linted source file.
+    fn lint(&self) -> miette::Result<(Vec<LintError>, BTreeMap<SourceId, PathBuf>)> {
+        let lints = self.selector.clone().select(self.config)?;
+
+        let mut linter = Linter::new(lints).with_config(self.config);
+        if let Some(namespace) = self.namespace {
+            linter = linter.with_namespace(namespace.clone());
+        }
+
+        let source_manager = DefaultSourceManager::default();
+        let mut paths = BTreeMap::new();
+
+        for file in self.masm_files {
+            let source = std::fs::read(file).map_err(|err| {
+                Report::msg(format!("failed to open file {}: {err}", file.display()))
+            })?;
+            let source_content = String::from_utf8(source)
+                .map_err(|err| Report::msg(format!("failed to decode file as UTF-8: {err}")))?;
+
+            let relative_file_path = file
+                .strip_prefix(self.base_path)
+                .expect("file should contain base path as a prefix");
+            let file_name = format!("{}", relative_file_path.display());
+            let source_file = source_manager.load(&file_name, source_content);
+
+            paths.insert(source_file.id(), file.clone());
+            linter.lint(source_file);
+        }
+
+        let errors = match linter.finish() {
+            Ok(()) => Vec::new(),
+            Err(err) => err.into_errors(),
+        };
+
+        Ok((errors, paths))
+    }
+}
+
+/// Reports the errors in the given format.
+///
+/// Returns a failure exit code if any error has error severity, or if any error has warning
+/// severity and `deny_warnings` is set.
+fn report_errors(
+    errors: Vec<LintError>,
+    format: OutputFormat,
+    deny_warnings: bool,
+) -> miette::Result<ExitCode> {
+    let is_failure = if deny_warnings {
+        !errors.is_empty()
+    } else {
+        errors.iter().any(|error| error.severity() == Severity::Error)
+    };
+
+    match format {
+        OutputFormat::Human => {
+            if !errors.is_empty() {
+                eprintln!("{:?}", Report::from(LinterError::new_lints(errors)));
+            }
+        },
+        OutputFormat::Json => {
+            let findings: Vec<Finding> = errors.iter().map(Finding::from).collect();
+            print_json(&findings)?;
+        },
+        OutputFormat::Sarif => {
+            print_json(&SarifLog::new(&registered_lints(), &errors))?;
+        },
+    }
 
-    let mut linter = Linter::new(lints);
+    Ok(if is_failure {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
 
-    for (file_idx, file) in masm_files.into_iter().enumerate() {
-        let source = std::fs::read(&file)
-            .map_err(|err| Report::msg(format!("failed to open file {}: {err}", file.display())))?;
-        let source_content = String::from_utf8(source)
-            .map_err(|err| Report::msg(format!("failed to decode file as UTF-8: {err}")))?;
+/// Parses the `--namespace` argument into a library path.
+///
+/// `LibraryPath` only accepts the kernel namespace under its reserved name `#sys`, so `$kernel`,
+/// the namespace under which kernel modules are usually referred to, is mapped to it explicitly.
+fn parse_namespace(namespace: &str) -> miette::Result<LibraryPath> {
+    if namespace == "$kernel" {
+        return Ok(LibraryPath::new_from_components(LibraryNamespace::Kernel, []));
+    }
 
-        let relative_file_path = file
-            .strip_prefix(source_path.as_path())
-            .expect("file should contain source path as a prefix");
-        let file_name = format!("{}", relative_file_path.display());
-        let id = SourceId::try_from(file_idx)
-            .expect("system limit: source manager has exhausted its supply of source ids");
-        let source_file = SourceFile::new(id, file_name, source_content);
+    LibraryPath::new(namespace)
+        .map_err(|err| Report::msg(format!("failed to parse namespace `{namespace}`: {err}")))
+}
+
+fn print_json(value: &impl serde::Serialize) -> miette::Result<()> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|err| Report::msg(format!("failed to serialize findings: {err}")))?;
+    println!("{json}");
+    Ok(())
+}
+
+/// Applies all available fixes of the lint errors and writes the fixed files back to disk.
+///
+/// Returns the number of rewritten files and the errors that could not be fixed.
+fn fix_files(
+    errors: Vec<LintError>,
+    paths: &BTreeMap<SourceId, PathBuf>,
+) -> miette::Result<(usize, Vec<LintError>)> {
+    let (fixed_files, unfixed) = fix::apply_fixes(errors);
 
-        linter.lint(Arc::new(source_file))?;
+    for fixed_file in fixed_files.iter() {
+        let path = &paths[&fixed_file.source_file.id()];
+        fix::write_atomically(path, &fixed_file.content).map_err(|err| {
+            Report::msg(format!("failed to write fixed file {}: {err}", path.display()))
+        })?;
+        eprintln!("fixed {} finding(s) in {}", fixed_file.num_fixes, path.display());
     }
 
-    linter.finish().map_err(Report::from)
+    Ok((fixed_files.len(), unfixed))
 }
 
 /// Returns a vector with paths to all MASM files in the specified directory and recursive
@@ -0,0 +1,299 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use glob::Pattern;
+use miette::{Report, Result};
+use serde::{Deserialize, de::DeserializeOwned};
+
+use crate::Severity;
+
+/// The name of the configuration file that is discovered upward from the linted path.
+pub const CONFIG_FILE_NAME: &str = "masmlint.toml";
+
+/// The project configuration, usually loaded from a `masmlint.toml` file.
+///
+/// An example configuration looks like this:
+///
+/// ```toml
+/// include = ["asm/**/*.masm"]
+/// exclude = ["asm/tests/**"]
+///
+/// [lints]
+/// bare_assert = "warn"
+/// push_immediate = "allow"
+/// ```
+///
+/// Lints can also be configured with a table to pass lint-specific options, e.g.
+/// `max_instructions_per_line = { level = "deny", max = 2 }`. Options that the lint does not
+/// support are rejected when the lint is selected.
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// The directory relative to which the include and exclude globs are matched.
+    root: PathBuf,
+    lints: BTreeMap<String, LintConfig>,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+impl Config {
+    /// Searches for a config file in the directory of `path` and all its ancestors and loads the
+    /// first one that is found.
+    ///
+    /// Returns `None` if no config file exists.
+    pub fn discover(path: &Path) -> Result<Option<Self>> {
+        let start = if path.is_dir() { Some(path) } else { path.parent() };
+
+        for dir in start.into_iter().flat_map(Path::ancestors) {
+            let config_path = dir.join(CONFIG_FILE_NAME);
+            if config_path.is_file() {
+                return Self::load(&config_path).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Loads the config file at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|err| {
+            Report::msg(format!("failed to read config file {}: {err}", path.display()))
+        })?;
+        let root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        Self::parse(root, &content).map_err(|err| {
+            Report::msg(format!("failed to parse config file {}: {err}", path.display()))
+        })
+    }
+
+    /// Parses a config from its TOML representation, matching path globs relative to `root`.
+    pub fn parse(root: PathBuf, content: &str) -> Result<Self> {
+        let raw: RawConfig = toml::from_str(content).map_err(|err| Report::msg(err.to_string()))?;
+
+        let lints = raw
+            .lints
+            .into_iter()
+            .map(|(name, lint_config)| {
+                let lint_config = match lint_config {
+                    RawLintConfig::Level(level) => LintConfig {
+                        level: Some(level),
+                        options: LintOptions::default(),
+                    },
+                    RawLintConfig::Table { level, options } => LintConfig {
+                        level,
+                        options: LintOptions::new(options),
+                    },
+                };
+                (name, lint_config)
+            })
+            .collect();
+
+        Ok(Self {
+            root,
+            lints,
+            include: parse_patterns(&raw.include)?,
+            exclude: parse_patterns(&raw.exclude)?,
+        })
+    }
+
+    /// Returns the names of all lints that are configured.
+    pub fn lint_names(&self) -> impl Iterator<Item = &str> {
+        self.lints.keys().map(String::as_str)
+    }
+
+    /// Returns the configured level of the lint, if any.
+    pub fn level(&self, lint_name: &str) -> Option<Level> {
+        self.lints.get(lint_name).and_then(|lint_config| lint_config.level)
+    }
+
+    /// Returns the configured options of the lint, which are empty if none are configured.
+    pub fn options(&self, lint_name: &str) -> LintOptions {
+        self.lints
+            .get(lint_name)
+            .map(|lint_config| lint_config.options.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the file at `path` should be linted according to the include and exclude
+    /// globs.
+    ///
+    /// A file is included if it matches any include glob, or if no include globs are configured,
+    /// and does not match any exclude glob.
+    pub fn is_included(&self, path: &Path) -> bool {
+        let path = path.strip_prefix(&self.root).unwrap_or(path);
+
+        let included = self.include.is_empty()
+            || self.include.iter().any(|pattern| pattern.matches_path(path));
+        let excluded = self.exclude.iter().any(|pattern| pattern.matches_path(path));
+
+        included && !excluded
+    }
+}
+
+/// The level at which a lint is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    /// The lint is not run.
+    Allow,
+    /// Findings of the lint are reported as warnings.
+    Warn,
+    /// Findings of the lint are reported as errors.
+    Deny,
+}
+
+impl Level {
+    /// Returns the severity with which findings are reported at this level, or `None` if the lint
+    /// is not run.
+    pub fn severity(&self) -> Option<Severity> {
+        match self {
+            Level::Allow => None,
+            Level::Warn => Some(Severity::Warning),
+            Level::Deny => Some(Severity::Error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct LintConfig {
+    level: Option<Level>,
+    options: LintOptions,
+}
+
+/// The lint-specific options from the config file.
+#[derive(Debug, Clone, Default)]
+pub struct LintOptions {
+    options: toml::Table,
+    /// The names of the options that were read with [`LintOptions::get`].
+    read: RefCell<BTreeSet<String>>,
+}
+
+impl LintOptions {
+    fn new(options: toml::Table) -> Self {
+        Self { options, read: RefCell::default() }
+    }
+
+    /// Returns the option with the given name deserialized into `T`, or `None` if the option is
+    /// not set.
+    pub fn get<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>> {
+        self.read.borrow_mut().insert(name.to_owned());
+        self.options
+            .get(name)
+            .cloned()
+            .map(|value| {
+                value.try_into().map_err(|err| {
+                    Report::msg(format!("failed to deserialize lint option `{name}`: {err}"))
+                })
+            })
+            .transpose()
+    }
+
+    /// Returns an error if an option is set that was never read, which means it is misspelled or
+    /// not supported by the lint.
+    pub fn ensure_all_read(&self) -> Result<()> {
+        let read = self.read.borrow();
+        match self.options.keys().find(|name| !read.contains(name.as_str())) {
+            Some(name) => Err(Report::msg(format!("unknown lint option `{name}`"))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfig {
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+    #[serde(default)]
+    lints: BTreeMap<String, RawLintConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawLintConfig {
+    Level(Level),
+    Table {
+        level: Option<Level>,
+        #[serde(flatten)]
+        options: toml::Table,
+    },
+}
+
+fn parse_patterns(patterns: &[String]) -> Result<Vec<Pattern>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Pattern::new(pattern)
+                .map_err(|err| Report::msg(format!("invalid path glob `{pattern}`: {err}")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> Config {
+        Config::parse(PathBuf::from("/project"), content).expect("config should parse")
+    }
+
+    #[test]
+    fn levels_are_parsed_from_strings_and_tables() {
+        let config = parse(
+            r#"
+            [lints]
+            bare_assert = "warn"
+            push_immediate = { level = "allow" }
+            max_instructions_per_line = { max = 2 }
+            "#,
+        );
+
+        assert_eq!(config.level("bare_assert"), Some(Level::Warn));
+        assert_eq!(config.level("push_immediate"), Some(Level::Allow));
+        assert_eq!(config.level("max_instructions_per_line"), None);
+        assert_eq!(config.level("empty_block"), None);
+        assert_eq!(Level::Deny.severity(), Some(Severity::Error));
+        assert!(Config::parse(PathBuf::new(), "[lints]\nbare_assert = \"warning\"\n").is_err());
+    }
+
+    #[test]
+    fn misspelled_options_are_rejected() {
+        let config = parse(
+            r#"
+            [lints]
+            max_instructions_per_line = { level = "deny", maxx = 2 }
+            "#,
+        );
+
+        let options = config.options("max_instructions_per_line");
+        assert_eq!(options.get::<usize>("max").unwrap(), None);
+        let err = options.ensure_all_read().unwrap_err();
+        assert!(err.to_string().contains("`maxx`"), "{err}");
+
+        let config = parse("[lints]\nmax_instructions_per_line = { max = 2 }\n");
+        let options = config.options("max_instructions_per_line");
+        assert_eq!(options.get::<usize>("max").unwrap(), Some(2));
+        assert!(options.ensure_all_read().is_ok());
+    }
+
+    #[test]
+    fn globs_are_matched_relative_to_the_config_root() {
+        let config = parse(
+            r#"
+            include = ["asm/**/*.masm"]
+            exclude = ["asm/tests/**"]
+            "#,
+        );
+
+        assert!(config.is_included(Path::new("/project/asm/account.masm")));
+        assert!(config.is_included(Path::new("/project/asm/note/mod.masm")));
+        assert!(!config.is_included(Path::new("/project/asm/tests/account.masm")));
+        assert!(!config.is_included(Path::new("/project/lib/account.masm")));
+        assert!(!config.is_included(Path::new("/other/asm/account.masm")));
+    }
+}
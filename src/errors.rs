@@ -1,10 +1,120 @@
-use std::sync::Arc;
+use std::{fmt, sync::Arc};
 
 use miden_assembly::{SourceFile, SourceSpan, ast::Instruction};
-use miette::Diagnostic;
+use miette::{Diagnostic, LabeledSpan, SourceCode};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Fix,
+    lints::{
+        BareAssert, ConstantCondition, DuplicateConstant, EmptyBlock, ErrorConstant, ErrorMessage,
+        ManualAssert, MaxInstructionsPerLine, NegatedComparison, PushImmediate, RedundantShuffle,
+        StackCommentDrift, UnreachableCode, UnusedConstant, UnusedImport, UnusedProcedure,
+        UnusedSuppression, VerboseShuffle,
+    },
+    parser::PARSE_ERROR,
+};
+
+/// The severity with which a finding is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl From<Severity> for miette::Severity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Warning => miette::Severity::Warning,
+            Severity::Error => miette::Severity::Error,
+        }
+    }
+}
+
+/// A finding of a lint, reported with a severity.
+#[derive(Debug)]
+pub struct LintError {
+    kind: LintErrorKind,
+    severity: Severity,
+}
+
+impl LintError {
+    pub fn new(kind: LintErrorKind, severity: Severity) -> Self {
+        Self { kind, severity }
+    }
+
+    pub fn kind(&self) -> &LintErrorKind {
+        &self.kind
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Returns the name of the lint that reported this error.
+    pub fn lint_name(&self) -> &'static str {
+        self.kind.lint_name()
+    }
+
+    pub fn span(&self) -> SourceSpan {
+        self.kind.span()
+    }
+
+    pub fn source_file(&self) -> &Arc<SourceFile> {
+        self.kind.source_file()
+    }
+
+    /// Returns the fix that resolves this error, if it can be fixed automatically.
+    pub fn fix(&self) -> Option<Fix> {
+        self.kind.fix()
+    }
+}
+
+impl fmt::Display for LintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.kind, f)
+    }
+}
+
+impl std::error::Error for LintError {}
+
+impl Diagnostic for LintError {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.kind.code()
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        Some(self.severity.into())
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.kind.help()
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.kind.url()
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        self.kind.source_code()
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        self.kind.labels()
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        self.kind.related()
+    }
+
+    fn diagnostic_source(&self) -> Option<&dyn Diagnostic> {
+        self.kind.diagnostic_source()
+    }
+}
 
 #[derive(Debug, thiserror::Error, Diagnostic)]
-pub enum LintError {
+pub enum LintErrorKind {
     #[error("operand is pushed before an instruction that can take an immediate value")]
     #[diagnostic(help("use the instruction in its immediate form `{alternative}`"))]
     PushImmediate {
@@ -25,10 +135,344 @@ pub enum LintError {
         #[source_code]
         source_file: Arc<SourceFile>,
     },
+    #[error("comparison is negated with `not`")]
+    #[diagnostic(help("use the opposite comparison `{alternative}`"))]
+    NegatedComparison {
+        #[label("can be written as a single comparison")]
+        span: SourceSpan,
+        alternative: String,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("branch only fails, which can be written as an assertion")]
+    #[diagnostic(help(
+        "replace the `if` with `{alternative}`{}",
+        other_branch_help(.keeps_other_branch)
+    ))]
+    ManualAssert {
+        #[label("one branch of this `if` always fails")]
+        span: SourceSpan,
+        alternative: String,
+        /// Whether the branch that does not fail contains code, which must follow the assertion.
+        keeps_other_branch: bool,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("line has more than {max} instructions")]
+    #[diagnostic(help("split the line into multiple lines with at most {max} instructions each"))]
+    MaxInstructionsPerLine {
+        #[label("more than {max} instructions on this line")]
+        span: SourceSpan,
+        max: usize,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("stack manipulations cancel each other out")]
+    #[diagnostic(help("remove the stack manipulations, since they leave the stack unchanged"))]
+    RedundantShuffle {
+        #[label("leaves the stack unchanged")]
+        span: SourceSpan,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("stack manipulation can be written as a single shorter instruction")]
+    #[diagnostic(help("use `{alternative}` instead"))]
+    VerboseShuffle {
+        #[label("can be written as `{alternative}`")]
+        span: SourceSpan,
+        alternative: String,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("private procedure `{name}` is never used")]
+    #[diagnostic(help("remove the procedure, or invoke it where it is needed"))]
+    UnusedProcedure {
+        #[label("not invoked by any exported procedure of this module")]
+        span: SourceSpan,
+        name: String,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("unused import `{path}`")]
+    #[diagnostic(help("remove the import"))]
+    UnusedImport {
+        #[label("`{name}` is never used")]
+        span: SourceSpan,
+        name: String,
+        path: String,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("constant `{name}` is never used")]
+    #[diagnostic(help("remove the constant, or use it where its value is needed"))]
+    UnusedConstant {
+        #[label("not referenced by any instruction or constant of this module")]
+        span: SourceSpan,
+        name: String,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("constant `{name}` has the same value as `{original}`")]
+    #[diagnostic(help("use a distinct value for `{name}`, or use `{original}` instead"))]
+    DuplicateConstant {
+        #[label("same value as `{original}`")]
+        span: SourceSpan,
+        name: String,
+        original: String,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("{reason}")]
+    #[diagnostic(help(
+        "describe the condition that failed in a message that is unique to the assertion, \
+         preferably defined as a named constant"
+    ))]
+    ErrorMessage {
+        #[label("{reason}")]
+        span: SourceSpan,
+        reason: &'static str,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("error constant `{name}` does not match the naming pattern `{pattern}`")]
+    #[diagnostic(help("rename the constant to match `{pattern}`"))]
+    ErrorConstantName {
+        #[label("does not match `{pattern}`")]
+        span: SourceSpan,
+        name: String,
+        pattern: String,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("error constant pattern `{pattern}` is invalid for module `{module}`: {reason}")]
+    #[diagnostic(help("change the `pattern` option of the `error_constant` lint"))]
+    InvalidErrorConstantPattern {
+        #[label("error constant of `{module}`")]
+        span: SourceSpan,
+        pattern: String,
+        module: String,
+        reason: String,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("error constant `{name}` has a different message than in `{other_module}`")]
+    #[diagnostic(help(
+        "use the same message as in `{other_module}`, which is \"{other_message}\", or rename \
+         the constant"
+    ))]
+    InconsistentErrorConstant {
+        #[label("message differs from the definition in `{other_module}`")]
+        span: SourceSpan,
+        name: String,
+        other_module: String,
+        other_message: String,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("unreachable code after an assertion that always fails")]
+    #[diagnostic(help("remove the unreachable code, or the assertion if it is not meant to fail"))]
+    UnreachableCode {
+        #[label("this code is never executed")]
+        span: SourceSpan,
+        #[label("this assertion always fails")]
+        failure: SourceSpan,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("control flow is known statically")]
+    #[diagnostic(help("{suggestion}"))]
+    ConstantCondition {
+        #[label("always executes the same code")]
+        span: SourceSpan,
+        suggestion: &'static str,
+        /// The code that replaces the statement, if it can be inlined automatically.
+        replacement: Option<String>,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("{block} is empty")]
+    #[diagnostic(help("{suggestion}"))]
+    EmptyBlock {
+        #[label("only contains `nop`")]
+        span: SourceSpan,
+        /// The description of the empty block, e.g. "`else` branch".
+        block: &'static str,
+        suggestion: &'static str,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("unused suppression of lint `{lint_name}`")]
+    #[diagnostic(help("remove `{lint_name}` from the suppression comment"))]
+    UnusedSuppression {
+        #[label("no finding of this lint is suppressed")]
+        span: SourceSpan,
+        lint_name: String,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("stack comment does not match the stack produced by the code")]
+    #[diagnostic(help(
+        "the code produces the stack `{actual}`; update the comment or fix the code"
+    ))]
+    StackCommentDrift {
+        #[label("{reason}")]
+        span: SourceSpan,
+        reason: String,
+        actual: String,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+    #[error("failed to parse module: {message}")]
+    ParseError {
+        #[label("{label}")]
+        span: SourceSpan,
+        message: String,
+        label: String,
+        #[source_code]
+        source_file: Arc<SourceFile>,
+    },
+}
+
+impl LintErrorKind {
+    /// Returns the name of the lint that reported this error.
+    pub fn lint_name(&self) -> &'static str {
+        match self {
+            LintErrorKind::PushImmediate { .. } => PushImmediate::NAME,
+            LintErrorKind::BareAssert { .. } => BareAssert::NAME,
+            LintErrorKind::StackCommentDrift { .. } => StackCommentDrift::NAME,
+            LintErrorKind::NegatedComparison { .. } => NegatedComparison::NAME,
+            LintErrorKind::ManualAssert { .. } => ManualAssert::NAME,
+            LintErrorKind::MaxInstructionsPerLine { .. } => MaxInstructionsPerLine::NAME,
+            LintErrorKind::RedundantShuffle { .. } => RedundantShuffle::NAME,
+            LintErrorKind::VerboseShuffle { .. } => VerboseShuffle::NAME,
+            LintErrorKind::UnusedProcedure { .. } => UnusedProcedure::NAME,
+            LintErrorKind::UnusedImport { .. } => UnusedImport::NAME,
+            LintErrorKind::UnusedConstant { .. } => UnusedConstant::NAME,
+            LintErrorKind::DuplicateConstant { .. } => DuplicateConstant::NAME,
+            LintErrorKind::ErrorMessage { .. } => ErrorMessage::NAME,
+            LintErrorKind::ErrorConstantName { .. }
+            | LintErrorKind::InvalidErrorConstantPattern { .. }
+            | LintErrorKind::InconsistentErrorConstant { .. } => ErrorConstant::NAME,
+            LintErrorKind::UnreachableCode { .. } => UnreachableCode::NAME,
+            LintErrorKind::ConstantCondition { .. } => ConstantCondition::NAME,
+            LintErrorKind::EmptyBlock { .. } => EmptyBlock::NAME,
+            LintErrorKind::UnusedSuppression { .. } => UnusedSuppression::NAME,
+            LintErrorKind::ParseError { .. } => PARSE_ERROR,
+        }
+    }
+
+    pub fn span(&self) -> SourceSpan {
+        match self {
+            LintErrorKind::PushImmediate { span, .. } => *span,
+            LintErrorKind::BareAssert { span, .. } => *span,
+            LintErrorKind::StackCommentDrift { span, .. } => *span,
+            LintErrorKind::NegatedComparison { span, .. } => *span,
+            LintErrorKind::ManualAssert { span, .. } => *span,
+            LintErrorKind::MaxInstructionsPerLine { span, .. } => *span,
+            LintErrorKind::RedundantShuffle { span, .. } => *span,
+            LintErrorKind::VerboseShuffle { span, .. } => *span,
+            LintErrorKind::UnusedProcedure { span, .. } => *span,
+            LintErrorKind::UnusedImport { span, .. } => *span,
+            LintErrorKind::UnusedConstant { span, .. } => *span,
+            LintErrorKind::DuplicateConstant { span, .. } => *span,
+            LintErrorKind::ErrorMessage { span, .. } => *span,
+            LintErrorKind::ErrorConstantName { span, .. } => *span,
+            LintErrorKind::InvalidErrorConstantPattern { span, .. } => *span,
+            LintErrorKind::InconsistentErrorConstant { span, .. } => *span,
+            LintErrorKind::UnreachableCode { span, .. } => *span,
+            LintErrorKind::ConstantCondition { span, .. } => *span,
+            LintErrorKind::EmptyBlock { span, .. } => *span,
+            LintErrorKind::UnusedSuppression { span, .. } => *span,
+            LintErrorKind::ParseError { span, .. } => *span,
+        }
+    }
+
+    pub fn source_file(&self) -> &Arc<SourceFile> {
+        match self {
+            LintErrorKind::PushImmediate { source_file, .. } => source_file,
+            LintErrorKind::BareAssert { source_file, .. } => source_file,
+            LintErrorKind::StackCommentDrift { source_file, .. } => source_file,
+            LintErrorKind::NegatedComparison { source_file, .. } => source_file,
+            LintErrorKind::ManualAssert { source_file, .. } => source_file,
+            LintErrorKind::MaxInstructionsPerLine { source_file, .. } => source_file,
+            LintErrorKind::RedundantShuffle { source_file, .. } => source_file,
+            LintErrorKind::VerboseShuffle { source_file, .. } => source_file,
+            LintErrorKind::UnusedProcedure { source_file, .. } => source_file,
+            LintErrorKind::UnusedImport { source_file, .. } => source_file,
+            LintErrorKind::UnusedConstant { source_file, .. } => source_file,
+            LintErrorKind::DuplicateConstant { source_file, .. } => source_file,
+            LintErrorKind::ErrorMessage { source_file, .. } => source_file,
+            LintErrorKind::ErrorConstantName { source_file, .. } => source_file,
+            LintErrorKind::InvalidErrorConstantPattern { source_file, .. } => source_file,
+            LintErrorKind::InconsistentErrorConstant { source_file, .. } => source_file,
+            LintErrorKind::UnreachableCode { source_file, .. } => source_file,
+            LintErrorKind::ConstantCondition { source_file, .. } => source_file,
+            LintErrorKind::EmptyBlock { source_file, .. } => source_file,
+            LintErrorKind::UnusedSuppression { source_file, .. } => source_file,
+            LintErrorKind::ParseError { source_file, .. } => source_file,
+        }
+    }
+
+    /// Returns the fix that resolves this error, if it can be fixed automatically.
+    pub fn fix(&self) -> Option<Fix> {
+        match self {
+            LintErrorKind::PushImmediate { span, alternative, source_file } => {
+                // The span only covers the whole push instruction if the pushed value is the first
+                // one of the push instruction, e.g. in `push.1.2 lt` the span starts at `2`.
+                let source = source_file.source_slice(span.into_slice_index())?;
+                if !source.starts_with("push") {
+                    return None;
+                }
+                Fix::unless_commented(source_file, *span, alternative.as_str())
+            },
+            LintErrorKind::NegatedComparison { span, alternative, source_file }
+            | LintErrorKind::VerboseShuffle { span, alternative, source_file }
+            | LintErrorKind::ManualAssert {
+                span,
+                alternative,
+                keeps_other_branch: false,
+                source_file,
+            } => Fix::unless_commented(source_file, *span, alternative.as_str()),
+            LintErrorKind::RedundantShuffle { span, source_file } => {
+                Fix::unless_commented(source_file, *span, "")
+            },
+            LintErrorKind::ConstantCondition {
+                span,
+                replacement: Some(replacement),
+                source_file,
+                ..
+            } => Fix::unless_commented(source_file, *span, replacement.as_str()),
+            LintErrorKind::UnusedImport { span, source_file, .. } => {
+                Some(Fix::removal(source_file, *span))
+            },
+            // There is no correct error message to insert, so the example message is only shown
+            // in the help.
+            LintErrorKind::BareAssert { .. } => None,
+            LintErrorKind::ManualAssert { .. }
+            | LintErrorKind::MaxInstructionsPerLine { .. }
+            | LintErrorKind::StackCommentDrift { .. }
+            | LintErrorKind::UnusedProcedure { .. }
+            | LintErrorKind::UnusedConstant { .. }
+            | LintErrorKind::DuplicateConstant { .. }
+            | LintErrorKind::ErrorMessage { .. }
+            | LintErrorKind::ErrorConstantName { .. }
+            | LintErrorKind::InvalidErrorConstantPattern { .. }
+            | LintErrorKind::InconsistentErrorConstant { .. }
+            | LintErrorKind::UnreachableCode { .. }
+            | LintErrorKind::ConstantCondition { .. }
+            | LintErrorKind::EmptyBlock { .. }
+            | LintErrorKind::UnusedSuppression { .. }
+            | LintErrorKind::ParseError { .. } => None,
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error, Diagnostic)]
-#[error("one or more lints failed")]
+#[error(
+    "linting found {} error(s) and {} warning(s)",
+    count_severity(.errors, Severity::Error),
+    count_severity(.errors, Severity::Warning)
+)]
 pub struct LinterError {
     #[related]
     errors: Vec<LintError>,
@@ -42,4 +486,24 @@ impl LinterError {
     pub fn new_lints(errors: Vec<LintError>) -> Self {
         Self { errors }
     }
+
+    pub fn errors(&self) -> &[LintError] {
+        &self.errors
+    }
+
+    pub fn into_errors(self) -> Vec<LintError> {
+        self.errors
+    }
+}
+
+fn other_branch_help(keeps_other_branch: &bool) -> &'static str {
+    if *keeps_other_branch {
+        ", followed by the code of the branch that does not fail"
+    } else {
+        ""
+    }
+}
+
+fn count_severity(errors: &[LintError], severity: Severity) -> usize {
+    errors.iter().filter(|error| error.severity() == severity).count()
 }
@@ -0,0 +1,244 @@
+use std::{collections::BTreeMap, fs, io, path::Path, sync::Arc};
+
+use miden_assembly::{SourceFile, SourceId, SourceSpan};
+
+use crate::LintError;
+
+/// A suggested edit that replaces the source code covered by `span` with `replacement`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    span: SourceSpan,
+    replacement: String,
+}
+
+impl Fix {
+    pub fn new(span: SourceSpan, replacement: impl Into<String>) -> Self {
+        Self { span, replacement: replacement.into() }
+    }
+
+    /// Creates a fix that removes the code covered by `span`, including the whole line if the code
+    /// is the only content on it.
+    pub fn removal(source_file: &SourceFile, span: SourceSpan) -> Self {
+        let source = source_file.as_str();
+        let range = span.into_slice_index();
+        let line_start = source[..range.start].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end =
+            source[range.end..].find('\n').map_or(source.len(), |idx| range.end + idx + 1);
+
+        let is_only_content = source[line_start..range.start].trim().is_empty()
+            && source[range.end..line_end].trim().is_empty();
+        let range = if is_only_content { line_start..line_end } else { range };
+
+        Self::new(SourceSpan::new(span.source_id(), range.start as u32..range.end as u32), "")
+    }
+
+    /// Creates a fix that replaces the code covered by `span`, unless the code contains a comment,
+    /// which would be lost. An empty replacement removes the code like [`Fix::removal`].
+    pub fn unless_commented(
+        source_file: &SourceFile,
+        span: SourceSpan,
+        replacement: impl Into<String>,
+    ) -> Option<Self> {
+        let source = source_file.source_slice(span.into_slice_index())?;
+        if source.contains('#') {
+            return None;
+        }
+
+        let replacement = replacement.into();
+        if replacement.is_empty() {
+            Some(Self::removal(source_file, span))
+        } else {
+            Some(Self::new(span, replacement))
+        }
+    }
+
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+}
+
+/// The content of a source file after fixes have been applied to it.
+#[derive(Debug)]
+pub struct FixedFile {
+    pub source_file: Arc<SourceFile>,
+    pub content: String,
+    pub num_fixes: usize,
+}
+
+/// Applies the fixes of the given errors to the text of the source files they were reported in.
+///
+/// Returns the fixed files and all errors that could not be fixed, either because they have no
+/// fix or because their fix overlaps with another fix in the same file. The latter can be fixed
+/// by running the fixer again on the fixed files.
+pub fn apply_fixes(errors: Vec<LintError>) -> (Vec<FixedFile>, Vec<LintError>) {
+    let mut fixes_by_file: BTreeMap<SourceId, Vec<(Fix, LintError)>> = BTreeMap::new();
+    let mut unfixed = Vec::new();
+
+    for error in errors {
+        match error.fix() {
+            Some(fix) => {
+                fixes_by_file.entry(error.source_file().id()).or_default().push((fix, error));
+            },
+            None => unfixed.push(error),
+        }
+    }
+
+    let mut fixed_files = Vec::new();
+    for mut fixes in fixes_by_file.into_values() {
+        let source_file = Arc::clone(fixes[0].1.source_file());
+        fixes.sort_by_key(|(fix, _)| (fix.span.start(), fix.span.end()));
+
+        let source = source_file.as_str();
+        let mut content = String::with_capacity(source.len());
+        let mut num_fixes = 0;
+        // The end of the last applied fix, or the offset up to which the source has been copied.
+        let mut cursor = 0;
+
+        for (fix, error) in fixes {
+            let range = fix.span.into_slice_index();
+            // Skip fixes that overlap with an already applied one.
+            if range.start < cursor {
+                unfixed.push(error);
+                continue;
+            }
+
+            content.push_str(&source[cursor..range.start]);
+            content.push_str(&fix.replacement);
+            cursor = range.end;
+            num_fixes += 1;
+        }
+        content.push_str(&source[cursor..]);
+
+        fixed_files.push(FixedFile { source_file, content, num_fixes });
+    }
+
+    (fixed_files, unfixed)
+}
+
+/// Writes `content` to the file at `path` by writing it to a temporary file in the same directory
+/// first and then renaming it, so the file is never left partially written.
+pub fn write_atomically(path: &Path, content: &str) -> io::Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::other(format!("{} is not a file", path.display())))?;
+    let mut tmp_file_name = file_name.to_owned();
+    tmp_file_name.push(".masmlint.tmp");
+    let tmp_path = path.with_file_name(tmp_file_name);
+
+    let permissions = fs::metadata(path)?.permissions();
+
+    fs::write(&tmp_path, content)?;
+    fs::set_permissions(&tmp_path, permissions)
+        .and_then(|_| fs::rename(&tmp_path, path))
+        .inspect_err(|_| {
+            let _ = fs::remove_file(&tmp_path);
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+
+    use miden_assembly::{DefaultSourceManager, SourceManager};
+
+    use super::*;
+    use crate::{LintErrorKind, Severity};
+
+    fn verbose_shuffle(
+        source_file: &Arc<SourceFile>,
+        range: Range<u32>,
+        alternative: &str,
+    ) -> LintError {
+        LintError::new(
+            LintErrorKind::VerboseShuffle {
+                span: SourceSpan::new(source_file.id(), range),
+                alternative: alternative.to_owned(),
+                source_file: Arc::clone(source_file),
+            },
+            Severity::Warning,
+        )
+    }
+
+    #[test]
+    fn fixes_are_applied_in_source_order() {
+        let source_manager = DefaultSourceManager::default();
+        let source_file = source_manager.load("test.masm", "swap.1 dup.0 add".to_owned());
+
+        let errors = vec![
+            verbose_shuffle(&source_file, 7..12, "dup"),
+            verbose_shuffle(&source_file, 0..6, "swap"),
+        ];
+        let (fixed_files, unfixed) = apply_fixes(errors);
+
+        assert!(unfixed.is_empty());
+        assert_eq!(fixed_files.len(), 1);
+        assert_eq!(fixed_files[0].content, "swap dup add");
+        assert_eq!(fixed_files[0].num_fixes, 2);
+    }
+
+    #[test]
+    fn overlapping_fixes_are_left_unfixed() {
+        let source_manager = DefaultSourceManager::default();
+        let source_file = source_manager.load("test.masm", "movup.2 movup.2 add".to_owned());
+
+        let errors = vec![
+            verbose_shuffle(&source_file, 0..15, "movdn.2"),
+            verbose_shuffle(&source_file, 8..19, "overlapping"),
+        ];
+        let (fixed_files, unfixed) = apply_fixes(errors);
+
+        assert_eq!(fixed_files[0].content, "movdn.2 add");
+        assert_eq!(fixed_files[0].num_fixes, 1);
+        assert_eq!(unfixed.len(), 1);
+        assert_eq!(unfixed[0].span(), SourceSpan::new(source_file.id(), 8..19));
+    }
+
+    #[test]
+    fn errors_without_fix_are_left_unfixed() {
+        let source_manager = DefaultSourceManager::default();
+        let source_file = source_manager.load("test.masm", "swap.1 # comment\n".to_owned());
+
+        // Fixes of spans that contain a comment are suppressed.
+        let errors = vec![verbose_shuffle(&source_file, 0..16, "swap")];
+        let (fixed_files, unfixed) = apply_fixes(errors);
+
+        assert!(fixed_files.is_empty());
+        assert_eq!(unfixed.len(), 1);
+    }
+
+    #[test]
+    fn push_immediate_is_not_fixed_across_a_comment() {
+        let source_manager = DefaultSourceManager::default();
+        let source = "push.5 # => [5, a]\nu32div\n";
+        let source_file = source_manager.load("test.masm", source.to_owned());
+
+        let error = LintError::new(
+            LintErrorKind::PushImmediate {
+                span: SourceSpan::new(source_file.id(), 0..25),
+                alternative: "u32div.5".to_owned(),
+                source_file: Arc::clone(&source_file),
+            },
+            Severity::Warning,
+        );
+        let (fixed_files, unfixed) = apply_fixes(vec![error]);
+
+        assert!(fixed_files.is_empty());
+        assert_eq!(unfixed.len(), 1);
+    }
+
+    #[test]
+    fn removal_removes_the_whole_line_if_the_span_is_its_only_content() {
+        let source_manager = DefaultSourceManager::default();
+        let source_file = source_manager.load("test.masm", "add\n    swap swap\nmul".to_owned());
+
+        let fix = Fix::removal(&source_file, SourceSpan::new(source_file.id(), 8..17));
+        assert_eq!(fix.span(), SourceSpan::new(source_file.id(), 4..18));
+
+        let fix = Fix::removal(&source_file, SourceSpan::new(source_file.id(), 8..12));
+        assert_eq!(fix.span(), SourceSpan::new(source_file.id(), 8..12));
+    }
+}
@@ -1,12 +1,39 @@
 extern crate alloc;
 
 mod errors;
-pub use errors::{LintError, LinterError};
+pub use errors::{LintError, LintErrorKind, LinterError, Severity};
+
+pub mod config;
+pub use config::Config;
+
+pub mod fix;
+pub use fix::Fix;
 
 pub mod lints;
 
 mod lint_selector;
-pub use lint_selector::LintSelector;
+pub use lint_selector::{LintSelector, registered_lints};
+
+pub mod report;
+
+pub mod sarif;
+
+pub mod parser;
+mod scanner;
+pub use parser::{ParsedModule, Parser};
+
+pub mod module_graph;
+pub use module_graph::{ModuleGraph, ResolvedTarget};
+
+pub mod peephole;
+pub use peephole::{Pattern, PatternMatch};
+
+pub mod stack_effect;
+pub use stack_effect::{StackEffect, StackEffectInference};
+
+mod suppression;
 
 mod linter;
-pub use linter::{EarlyContext, EarlyLintPass, Linter};
+pub use linter::{
+    Container, EarlyContext, EarlyLintPass, LateContext, LateLintPass, Lint, LintInfo, Linter,
+};
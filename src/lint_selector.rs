@@ -3,8 +3,14 @@ use std::collections::BTreeMap;
 use miette::{Report, Result};
 
 use crate::{
-    EarlyLintPass,
-    lints::{BareAssert, PushImmediate},
+    Lint, LintInfo,
+    config::{Config, Level, LintOptions},
+    lints::{
+        BareAssert, ConstantCondition, DuplicateConstant, EmptyBlock, ErrorConstant, ErrorMessage,
+        ManualAssert, MaxInstructionsPerLine, NegatedComparison, PushImmediate, RedundantShuffle,
+        StackCommentDrift, UnreachableCode, UnusedConstant, UnusedImport, UnusedProcedure,
+        UnusedSuppression, VerboseShuffle,
+    },
 };
 
 #[derive(Debug, Clone, Default)]
@@ -16,21 +22,36 @@ pub enum LintSelector {
 }
 
 impl LintSelector {
-    pub fn select(self) -> Result<Vec<Box<dyn EarlyLintPass>>> {
+    /// Selects the lints to run and constructs them with their options from the config.
+    ///
+    /// Lints that are set to `allow` in the config are not selected unless they are explicitly
+    /// selected, since the selector takes precedence over the config.
+    pub fn select(self, config: &Config) -> Result<Vec<Lint>> {
         let mut lints = all_lints();
-        match self {
-            LintSelector::All => Ok(lints.into_values().collect()),
+
+        for lint_name in config.lint_names() {
+            if !lints.contains_key(lint_name) {
+                return Err(Report::msg(format!("failed to configure unknown lint `{lint_name}`")));
+            }
+        }
+
+        let selected = match self {
+            LintSelector::All => {
+                lints.retain(|name, _| config.level(name) != Some(Level::Allow));
+                lints
+            },
             LintSelector::Select(selected) => {
-                let mut selected_lints = Vec::new();
+                let mut selected_lints = BTreeMap::new();
 
                 for selected_lint in selected {
-                    let lint = lints.remove(selected_lint.as_str()).ok_or_else(|| {
-                        Report::msg(format!("failed to select unknown lint `{selected_lint}`"))
-                    })?;
-                    selected_lints.push(lint);
+                    let (name, lint) =
+                        lints.remove_entry(selected_lint.as_str()).ok_or_else(|| {
+                            Report::msg(format!("failed to select unknown lint `{selected_lint}`"))
+                        })?;
+                    selected_lints.insert(name, lint);
                 }
 
-                Ok(selected_lints)
+                selected_lints
             },
             LintSelector::Exclude(excluded) => {
                 for excluded_lint in excluded {
@@ -39,23 +60,143 @@ impl LintSelector {
                     })?;
                 }
 
-                Ok(lints.into_values().collect())
+                lints.retain(|name, _| config.level(name) != Some(Level::Allow));
+                lints
             },
-        }
+        };
+
+        selected
+            .into_iter()
+            .map(|(name, new_lint)| {
+                let options = config.options(name);
+                new_lint(&options)
+                    .and_then(|lint| options.ensure_all_read().map(|()| lint))
+                    .map_err(|err| err.wrap_err(format!("failed to configure lint `{name}`")))
+            })
+            .collect()
     }
 }
 
-fn all_lints() -> BTreeMap<&'static str, Box<dyn EarlyLintPass>> {
+/// Returns the information of all lints known to the linter, ordered by name.
+pub fn registered_lints() -> Vec<LintInfo> {
+    all_lints()
+        .values()
+        .map(|new_lint| {
+            new_lint(&LintOptions::default())
+                .expect("lint should be constructible with default options")
+                .info()
+        })
+        .collect()
+}
+
+/// Returns `true` if a lint with the given name is known to the linter.
+pub(crate) fn is_registered(lint_name: &str) -> bool {
+    all_lints().contains_key(lint_name)
+}
+
+type NewLint = fn(&LintOptions) -> Result<Lint>;
+
+fn all_lints() -> BTreeMap<&'static str, NewLint> {
     BTreeMap::from_iter([
-        (BareAssert::NAME, bare_assert()),
-        (PushImmediate::NAME, push_immediate()),
+        (BareAssert::NAME, bare_assert as NewLint),
+        (ConstantCondition::NAME, constant_condition),
+        (DuplicateConstant::NAME, duplicate_constant),
+        (EmptyBlock::NAME, empty_block),
+        (ErrorConstant::NAME, error_constant),
+        (ErrorMessage::NAME, error_message),
+        (ManualAssert::NAME, manual_assert),
+        (MaxInstructionsPerLine::NAME, max_instructions_per_line),
+        (NegatedComparison::NAME, negated_comparison),
+        (PushImmediate::NAME, push_immediate),
+        (RedundantShuffle::NAME, redundant_shuffle),
+        (StackCommentDrift::NAME, stack_comment_drift),
+        (UnreachableCode::NAME, unreachable_code),
+        (UnusedConstant::NAME, unused_constant),
+        (UnusedImport::NAME, unused_import),
+        (UnusedProcedure::NAME, unused_procedure),
+        (UnusedSuppression::NAME, unused_suppression),
+        (VerboseShuffle::NAME, verbose_shuffle),
     ])
 }
 
-fn bare_assert() -> Box<dyn EarlyLintPass> {
-    Box::new(BareAssert)
+fn bare_assert(_options: &LintOptions) -> Result<Lint> {
+    Ok(Lint::Early(Box::new(BareAssert)))
+}
+
+fn constant_condition(_options: &LintOptions) -> Result<Lint> {
+    Ok(Lint::Early(Box::new(ConstantCondition)))
+}
+
+fn duplicate_constant(options: &LintOptions) -> Result<Lint> {
+    let error_messages_only = options.get("error_messages_only")?.unwrap_or(false);
+    Ok(Lint::Late(Box::new(DuplicateConstant::new(error_messages_only))))
+}
+
+fn empty_block(_options: &LintOptions) -> Result<Lint> {
+    Ok(Lint::Early(Box::new(EmptyBlock)))
+}
+
+fn error_constant(options: &LintOptions) -> Result<Lint> {
+    let pattern = options
+        .get("pattern")?
+        .unwrap_or_else(|| ErrorConstant::DEFAULT_PATTERN.to_owned());
+    Ok(Lint::Late(Box::new(ErrorConstant::new(pattern)?)))
+}
+
+fn error_message(options: &LintOptions) -> Result<Lint> {
+    let require_constants = options.get("require_constants")?.unwrap_or(false);
+    Ok(Lint::Early(Box::new(ErrorMessage::new(require_constants))))
+}
+
+fn manual_assert(_options: &LintOptions) -> Result<Lint> {
+    Ok(Lint::Early(Box::new(ManualAssert)))
+}
+
+fn max_instructions_per_line(options: &LintOptions) -> Result<Lint> {
+    let max = options.get("max")?.unwrap_or(MaxInstructionsPerLine::DEFAULT_MAX);
+    if max == 0 {
+        return Err(Report::msg("lint option `max` must be at least 1"));
+    }
+
+    Ok(Lint::Early(Box::new(MaxInstructionsPerLine::new(max))))
+}
+
+fn negated_comparison(_options: &LintOptions) -> Result<Lint> {
+    Ok(Lint::Early(Box::new(NegatedComparison::new())))
+}
+
+fn push_immediate(_options: &LintOptions) -> Result<Lint> {
+    Ok(Lint::Early(Box::new(PushImmediate::new())))
+}
+
+fn redundant_shuffle(_options: &LintOptions) -> Result<Lint> {
+    Ok(Lint::Early(Box::new(RedundantShuffle::new())))
+}
+
+fn stack_comment_drift(_options: &LintOptions) -> Result<Lint> {
+    Ok(Lint::Early(Box::new(StackCommentDrift::new())))
+}
+
+fn unreachable_code(_options: &LintOptions) -> Result<Lint> {
+    Ok(Lint::Early(Box::new(UnreachableCode)))
+}
+
+fn unused_constant(_options: &LintOptions) -> Result<Lint> {
+    Ok(Lint::Late(Box::new(UnusedConstant)))
+}
+
+fn unused_import(_options: &LintOptions) -> Result<Lint> {
+    Ok(Lint::Late(Box::new(UnusedImport)))
+}
+
+fn unused_procedure(_options: &LintOptions) -> Result<Lint> {
+    Ok(Lint::Late(Box::new(UnusedProcedure)))
+}
+
+fn unused_suppression(_options: &LintOptions) -> Result<Lint> {
+    Ok(Lint::Late(Box::new(UnusedSuppression)))
 }
 
-fn push_immediate() -> Box<dyn EarlyLintPass> {
-    Box::new(PushImmediate::new())
+fn verbose_shuffle(_options: &LintOptions) -> Result<Lint> {
+    Ok(Lint::Early(Box::new(VerboseShuffle::new())))
 }
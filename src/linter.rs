@@ -1,30 +1,86 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use miden_assembly::{
-    SourceFile, Span,
-    ast::{Block, Export, Form, Instruction, Op},
-    testing::TestContext,
+    LibraryPath, SourceFile, SourceId, SourceSpan, Span,
+    ast::{Block, Constant, Export, Import, Instruction, ModuleKind, Op, Procedure},
 };
-use miette::{Context, Result};
 
-use crate::{LintError, errors::LinterError};
+use crate::{
+    Config, LintError, LintErrorKind, ModuleGraph, ParsedModule, Parser, Severity,
+    StackEffectInference, errors::LinterError, lint_selector, lints::UnusedSuppression,
+    suppression::Suppressions,
+};
 
 pub struct Linter {
-    lints: Vec<Box<dyn EarlyLintPass>>,
-    errors: Vec<LintError>,
+    early_lints: Vec<Box<dyn EarlyLintPass>>,
+    late_lints: Vec<Box<dyn LateLintPass>>,
+    parser: Parser,
+    /// The parsed modules, which are linted by the late lints once all files have been linted.
+    modules: Vec<ParsedModule>,
+    reporter: Reporter,
 }
 
 impl Linter {
-    pub fn new(lints: Vec<Box<dyn EarlyLintPass>>) -> Self {
-        Self { lints, errors: Vec::new() }
+    pub fn new(lints: Vec<Lint>) -> Self {
+        let severities = lints
+            .iter()
+            .map(|lint| {
+                let info = lint.info();
+                (info.name, info.default_severity)
+            })
+            .collect();
+
+        let mut early_lints = Vec::new();
+        let mut late_lints = Vec::new();
+        for lint in lints {
+            match lint {
+                Lint::Early(lint) => early_lints.push(lint),
+                Lint::Late(lint) => late_lints.push(lint),
+            }
+        }
+
+        Self {
+            early_lints,
+            late_lints,
+            parser: Parser::default(),
+            modules: Vec::new(),
+            reporter: Reporter { severities, ..Default::default() },
+        }
+    }
+
+    /// Sets the severity with which findings of each lint are reported to the one of the level
+    /// configured for the lint, if any.
+    pub fn with_config(mut self, config: &Config) -> Self {
+        for (name, severity) in self.reporter.severities.iter_mut() {
+            if let Some(configured) = config.level(name).and_then(|level| level.severity()) {
+                *severity = configured;
+            }
+        }
+        self
+    }
+
+    /// Sets the path of the library the linted files belong to, e.g. `miden::kernels::tx`.
+    ///
+    /// The module path of each linted file is derived from this path and the name of the file
+    /// relative to the library root, which is used to resolve imports between the modules.
+    pub fn with_namespace(mut self, namespace: LibraryPath) -> Self {
+        self.parser = Parser::new(namespace);
+        self
     }
 
-    pub fn lint(&mut self, source: Arc<SourceFile>) -> Result<()> {
-        self.early_lint(Arc::clone(&source))
+    /// Parses and lints the source file.
+    ///
+    /// If the file cannot be parsed, the parse error is reported as a finding and the file is
+    /// skipped by all lints.
+    pub fn lint(&mut self, source: Arc<SourceFile>) {
+        self.early_lint(source)
     }
 
     pub fn finish(mut self) -> Result<(), LinterError> {
-        let errors = core::mem::take(&mut self.errors);
+        self.late_lint();
+        self.reporter.report_unused_suppressions();
+
+        let errors = core::mem::take(&mut self.reporter.errors);
 
         if errors.is_empty() {
             Ok(())
@@ -33,74 +89,392 @@ impl Linter {
         }
     }
 
-    fn early_lint(&mut self, source_file: Arc<SourceFile>) -> Result<()> {
-        // This is abusing the miden-assembly testing feature to be able to parse the forms,
-        // but there is no other public API to get the forms, unfortunately.
-        let forms = TestContext::new()
-            .parse_forms(Arc::clone(&source_file))
-            .context("failed to parse forms")?;
+    fn early_lint(&mut self, source_file: Arc<SourceFile>) {
+        let module = match self.parser.parse(Arc::clone(&source_file)) {
+            Ok(module) => module,
+            Err(error) => {
+                // Parse errors cannot be suppressed, since the suppression comments are only known
+                // once the module is parsed.
+                self.reporter.errors.push(LintError::new(error, Severity::Error));
+                return;
+            },
+        };
+
+        let mut reporter = core::mem::take(&mut self.reporter);
+        reporter
+            .suppressions
+            .insert(source_file.id(), Suppressions::parse(source_file, module.module()));
 
-        let errors = core::mem::take(&mut self.errors);
+        let lints = self.early_lints.as_mut_slice();
+        let mut early_ctx = EarlyContext {
+            reporter,
+            source_file: Arc::clone(module.source_file()),
+            container: None,
+            stack_effects: Arc::new(StackEffectInference::new(module.module())),
+            stack_depth: None,
+        };
 
-        let mut early_ctx = EarlyContext { errors, source_file };
+        for lint in lints.iter_mut() {
+            lint.check_module(&mut early_ctx, &module);
+        }
+
+        for import in module.imports() {
+            for lint in lints.iter_mut() {
+                lint.check_import(&mut early_ctx, import);
+            }
+        }
 
-        for form in forms {
-            let Form::Procedure(Export::Procedure(proc)) = form else {
+        for constant in module.constants() {
+            for lint in lints.iter_mut() {
+                lint.check_constant(&mut early_ctx, constant);
+            }
+        }
+
+        for export in module.procedures() {
+            let Export::Procedure(procedure) = export else {
                 continue;
             };
+            let container = if module.kind() == ModuleKind::Executable && procedure.is_entrypoint()
+            {
+                Container::Entrypoint
+            } else if procedure.visibility().is_exported() {
+                Container::ExportedProcedure
+            } else {
+                Container::PrivateProcedure
+            };
 
-            early_ctx.lint_block(proc.body(), self.lints.as_mut_slice());
+            early_ctx.container = Some(container);
+            early_ctx.stack_depth = Some(0);
+            early_ctx.lint_procedure(procedure, lints);
+            early_ctx.container = None;
+            early_ctx.stack_depth = None;
         }
 
-        // Put the errors back into the field.
-        core::mem::swap(&mut early_ctx.errors, &mut self.errors);
+        let reporter = early_ctx.reporter;
+
+        // Put the reporter back into the field.
+        self.reporter = reporter;
 
-        Ok(())
+        self.modules.push(module);
+    }
+
+    fn late_lint(&mut self) {
+        if self.late_lints.is_empty() {
+            return;
+        }
+
+        let mut late_ctx = LateContext {
+            reporter: core::mem::take(&mut self.reporter),
+            graph: ModuleGraph::new(core::mem::take(&mut self.modules)),
+        };
+
+        for lint in self.late_lints.iter_mut() {
+            lint.check_module_graph(&mut late_ctx);
+        }
+
+        // Put the reporter back into the field.
+        self.reporter = late_ctx.reporter;
     }
 }
 
-pub struct EarlyContext {
+/// Collects the findings of all lints, taking their configured severity and the suppression
+/// comments of the source files into account.
+#[derive(Default)]
+struct Reporter {
     errors: Vec<LintError>,
+    severities: BTreeMap<&'static str, Severity>,
+    suppressions: BTreeMap<SourceId, Suppressions>,
+}
+
+impl Reporter {
+    fn push_error(&mut self, error: LintErrorKind) {
+        let is_suppressed = self
+            .suppressions
+            .get_mut(&error.source_file().id())
+            .is_some_and(|suppressions| suppressions.suppress(&error));
+        if is_suppressed {
+            return;
+        }
+
+        let severity = self.severities.get(error.lint_name()).copied().unwrap_or(Severity::Error);
+        self.errors.push(LintError::new(error, severity));
+    }
+
+    /// Reports suppressions of lints that are run or unknown, but did not suppress any finding, if
+    /// the [`UnusedSuppression`] lint is run.
+    ///
+    /// Suppressions of known lints that are not run are not reported, since it is unknown whether
+    /// they would suppress anything.
+    fn report_unused_suppressions(&mut self) {
+        let suppressions = core::mem::take(&mut self.suppressions);
+        let Some(&severity) = self.severities.get(UnusedSuppression::NAME) else {
+            return;
+        };
+
+        for suppressions in suppressions.into_values() {
+            let source_file = Arc::clone(suppressions.source_file());
+
+            for (lint_name, span) in suppressions.into_unused() {
+                if self.severities.contains_key(lint_name.as_str())
+                    || !lint_selector::is_registered(&lint_name)
+                {
+                    self.errors.push(LintError::new(
+                        LintErrorKind::UnusedSuppression {
+                            span,
+                            lint_name,
+                            source_file: Arc::clone(&source_file),
+                        },
+                        severity,
+                    ));
+                }
+            }
+        }
+    }
+}
+pub struct EarlyContext {
+    reporter: Reporter,
     source_file: Arc<SourceFile>,
+    /// The kind of item whose body is currently linted, or `None` outside of a body.
+    container: Option<Container>,
+    stack_effects: Arc<StackEffectInference>,
+    /// The depth of the stack before the current instruction relative to the start of the
+    /// container, or `None` if it is unknown.
+    stack_depth: Option<isize>,
 }
 
 impl EarlyContext {
+    fn lint_procedure(&mut self, procedure: &Procedure, lints: &mut [Box<dyn EarlyLintPass>]) {
+        for lint in lints.iter_mut() {
+            lint.enter_procedure(self, procedure);
+        }
+
+        self.lint_block(procedure.body(), lints);
+
+        for lint in lints.iter_mut() {
+            lint.exit_procedure(self, procedure);
+        }
+    }
+
     fn lint_block(&mut self, block: &Block, lints: &mut [Box<dyn EarlyLintPass>]) {
         for lint in lints.iter_mut() {
-            lint.block_changed(block);
+            lint.enter_block(self, block);
         }
 
         for op in block.iter() {
             match op {
-                Op::If { then_blk, else_blk, .. } => {
+                Op::If { span, then_blk, else_blk } => {
+                    for lint in lints.iter_mut() {
+                        lint.check_if(self, *span, then_blk, else_blk);
+                    }
+
+                    let branch_depth = self.pop_condition();
                     self.lint_block(then_blk, lints);
+                    let then_depth = core::mem::replace(&mut self.stack_depth, branch_depth);
                     self.lint_block(else_blk, lints);
+
+                    if self.stack_depth != then_depth {
+                        self.stack_depth = None;
+                    }
                 },
-                Op::While { body, .. } => {
+                Op::While { span, body } => {
+                    for lint in lints.iter_mut() {
+                        lint.check_while(self, *span, body);
+                    }
+
+                    let loop_depth = self.pop_condition();
                     self.lint_block(body, lints);
+
+                    // Each iteration must push the condition of the next one.
+                    self.stack_depth = if self.stack_depth == loop_depth.map(|depth| depth + 1) {
+                        loop_depth
+                    } else {
+                        None
+                    };
                 },
-                Op::Repeat { body, .. } => {
+                Op::Repeat { span, count, body } => {
+                    for lint in lints.iter_mut() {
+                        lint.check_repeat(self, *span, *count, body);
+                    }
+
+                    let start_depth = self.stack_depth;
                     self.lint_block(body, lints);
+
+                    self.stack_depth = start_depth
+                        .zip(self.stack_depth)
+                        .map(|(start, end)| start + (end - start) * *count as isize);
                 },
                 Op::Inst(instr) => {
                     for lint in lints.iter_mut() {
                         lint.lint_instruction(self, instr);
                     }
+
+                    let effect = self.stack_effects.instruction(instr);
+                    self.stack_depth =
+                        self.stack_depth.zip(effect).map(|(depth, effect)| depth + effect.delta());
                 },
             }
         }
+
+        for lint in lints.iter_mut() {
+            lint.exit_block(self, block);
+        }
+    }
+
+    /// Pops the condition of an `if` or `while` off the stack and returns the resulting depth.
+    fn pop_condition(&mut self) -> Option<isize> {
+        self.stack_depth = self.stack_depth.map(|depth| depth - 1);
+        self.stack_depth
     }
 
-    pub fn push_error(&mut self, error: LintError) {
-        self.errors.push(error);
+    pub fn push_error(&mut self, error: LintErrorKind) {
+        self.reporter.push_error(error);
     }
 
     pub fn source_file(&self) -> Arc<SourceFile> {
         Arc::clone(&self.source_file)
     }
+
+    /// Returns the depth of the stack before the current instruction relative to the start of the
+    /// container, or `None` if it is unknown.
+    ///
+    /// For example, the depth is `-1` after `drop` at the start of a procedure and `2` after
+    /// `push.1 push.2`. It becomes unknown after an instruction whose effect is unknown, such as an
+    /// invocation of a procedure in another module, or after branches that leave the stack at
+    /// different depths.
+    pub fn stack_depth(&self) -> Option<isize> {
+        self.stack_depth
+    }
+
+    /// Returns the stack effects of the instructions and procedures of the current module.
+    pub fn stack_effects(&self) -> &StackEffectInference {
+        &self.stack_effects
+    }
+
+    /// Returns the kind of item whose body is currently linted, or `None` outside of a body, e.g.
+    /// when checking imports and constants.
+    pub fn container(&self) -> Option<Container> {
+        self.container
+    }
+}
+
+/// The kind of item whose body contains the linted code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Container {
+    /// The `begin ... end` block of an executable program.
+    Entrypoint,
+    /// A procedure that is exported from its module with `export`.
+    ExportedProcedure,
+    /// A procedure that is private to its module, defined with `proc`.
+    PrivateProcedure,
+}
+
+/// The context of late lints, which have access to all modules of the linted library.
+pub struct LateContext {
+    reporter: Reporter,
+    graph: ModuleGraph,
 }
 
+impl LateContext {
+    pub fn push_error(&mut self, error: LintErrorKind) {
+        self.reporter.push_error(error);
+    }
+
+    /// Returns the graph of all linted modules.
+    pub fn graph(&self) -> &ModuleGraph {
+        &self.graph
+    }
+}
+
+/// A lint that is run either on each module in isolation or on the whole library.
+pub enum Lint {
+    Early(Box<dyn EarlyLintPass>),
+    Late(Box<dyn LateLintPass>),
+}
+
+impl Lint {
+    pub fn info(&self) -> LintInfo {
+        match self {
+            Lint::Early(lint) => lint.info(),
+            Lint::Late(lint) => lint.info(),
+        }
+    }
+}
+
+/// Static information about a lint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LintInfo {
+    /// The name by which the lint is selected or excluded.
+    pub name: &'static str,
+    /// What the lint checks for.
+    pub description: &'static str,
+    /// How findings of the lint can generally be resolved.
+    pub help: &'static str,
+    /// The severity with which findings are reported unless configured otherwise.
+    pub default_severity: Severity,
+}
+
+/// A lint that is run on each module in isolation while its AST is traversed.
+///
+/// The hooks are called in the order of the traversal: [`check_module`](Self::check_module) once
+/// per module, followed by the imports, the constants and the procedures, each in the order they
+/// appear in the source. All hooks do nothing by default, so a lint only implements the ones it
+/// needs.
 pub trait EarlyLintPass {
-    fn lint_instruction(&mut self, early_ctx: &mut EarlyContext, instruction: &Span<Instruction>);
-    fn block_changed(&mut self, _block: &Block) {}
+    fn info(&self) -> LintInfo;
+    /// Called once for each module before any of its items are checked.
+    fn check_module(&mut self, _early_ctx: &mut EarlyContext, _module: &ParsedModule) {}
+    /// Called for each import of the module.
+    fn check_import(&mut self, _early_ctx: &mut EarlyContext, _import: &Import) {}
+    /// Called for each constant defined in the module.
+    fn check_constant(&mut self, _early_ctx: &mut EarlyContext, _constant: &Constant) {}
+    /// Called before the body of each procedure is linted, including the `begin` block of an
+    /// executable, which semantic analysis turns into a procedure named `main`.
+    ///
+    /// The kind of item the body belongs to is available through [`EarlyContext::container`].
+    fn enter_procedure(&mut self, _early_ctx: &mut EarlyContext, _procedure: &Procedure) {}
+    /// Called after the body of each procedure has been linted.
+    fn exit_procedure(&mut self, _early_ctx: &mut EarlyContext, _procedure: &Procedure) {}
+    /// Called for each block before its instructions are linted, including the bodies of
+    /// procedures and the branches and bodies of control flow.
+    fn enter_block(&mut self, _early_ctx: &mut EarlyContext, _block: &Block) {}
+    /// Called for each block after its instructions and nested blocks have been linted.
+    fn exit_block(&mut self, _early_ctx: &mut EarlyContext, _block: &Block) {}
+    /// Called for each `if` statement before its branches are linted.
+    ///
+    /// An `if.false` is passed with swapped branches, so `then_blk` is always the branch that is
+    /// executed if the condition is `1`. An omitted `else` branch is passed as a block with a
+    /// single `nop`.
+    fn check_if(
+        &mut self,
+        _early_ctx: &mut EarlyContext,
+        _span: SourceSpan,
+        _then_blk: &Block,
+        _else_blk: &Block,
+    ) {
+    }
+    /// Called for each `while` loop before its body is linted.
+    fn check_while(&mut self, _early_ctx: &mut EarlyContext, _span: SourceSpan, _body: &Block) {}
+    /// Called for each `repeat` loop before its body is linted.
+    fn check_repeat(
+        &mut self,
+        _early_ctx: &mut EarlyContext,
+        _span: SourceSpan,
+        _count: u32,
+        _body: &Block,
+    ) {
+    }
+    /// Called for each instruction.
+    fn lint_instruction(
+        &mut self,
+        _early_ctx: &mut EarlyContext,
+        _instruction: &Span<Instruction>,
+    ) {
+    }
+}
+
+/// A lint that runs after all files have been parsed and early-linted, with access to the module
+/// graph of the whole library, e.g. to check how procedures are used across modules.
+pub trait LateLintPass {
+    fn info(&self) -> LintInfo;
+    fn check_module_graph(&mut self, late_ctx: &mut LateContext);
 }
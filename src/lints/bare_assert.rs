@@ -5,7 +5,10 @@ use miden_assembly::{
     ast::{Immediate, Instruction},
 };
 
-use crate::{EarlyContext, LintError, linter::EarlyLintPass};
+use crate::{EarlyContext, LintErrorKind, LintInfo, Severity, linter::EarlyLintPass};
+
+/// The message of the assertion that is suggested as an example.
+pub(super) const EXAMPLE_ERROR_MESSAGE: &str = "helpful error message";
 
 pub struct BareAssert;
 
@@ -14,9 +17,19 @@ impl BareAssert {
 }
 
 impl EarlyLintPass for BareAssert {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks for assertions without error messages.",
+            help: "Use the assertion instruction with a helpful error message, e.g. \
+                   `assert.err=\"helpful error message\"`.",
+            default_severity: Severity::Error,
+        }
+    }
+
     fn lint_instruction(&mut self, early_ctx: &mut EarlyContext, instruction: &Span<Instruction>) {
         if let Some(assert_with_error) = match_assert_instruction(instruction) {
-            early_ctx.push_error(LintError::BareAssert {
+            early_ctx.push_error(LintErrorKind::BareAssert {
                 span: instruction.span(),
                 assert_with_error,
                 source_file: early_ctx.source_file(),
@@ -26,7 +39,8 @@ impl EarlyLintPass for BareAssert {
 }
 
 fn match_assert_instruction(instruction: &Span<Instruction>) -> Option<Instruction> {
-    let example_msg = "helpful error message";
+    // The message is quoted so the suggested instruction is valid MASM.
+    let example_msg = format!("\"{EXAMPLE_ERROR_MESSAGE}\"");
     let span = SourceSpan::at(SourceId::new(0), 0);
     let example_msg: Arc<str> = Arc::from(example_msg);
     let example_msg = Immediate::Value(Span::new(span, example_msg));
@@ -0,0 +1,166 @@
+use miden_assembly::{
+    SourceFile, SourceSpan, Spanned,
+    ast::{Block, Op},
+};
+
+use super::manual_assert::{is_empty, pushed_value};
+use crate::{EarlyContext, EarlyLintPass, LintErrorKind, LintInfo, Severity};
+
+/// Checks for control flow whose condition is known statically, i.e. an `if` or `while` whose
+/// condition is pushed right before it, such as `push.1 if.true`, and `repeat.1`.
+///
+/// The parser already rejects `repeat.0`. A `while` is only reported if its condition is `0`, since
+/// the condition of the following iterations is computed by the body.
+pub struct ConstantCondition;
+
+impl ConstantCondition {
+    pub const NAME: &'static str = "constant_condition";
+}
+
+impl EarlyLintPass for ConstantCondition {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks for `if`, `while` and `repeat` statements whose condition or \
+                          count is known statically.",
+            help: "Inline the code that is executed and remove the statement, e.g. `add` instead \
+                   of `push.1 if.true add end`.",
+            default_severity: Severity::Warning,
+        }
+    }
+
+    fn enter_block(&mut self, early_ctx: &mut EarlyContext, block: &Block) {
+        let source_file = early_ctx.source_file();
+        let ops = block.iter().as_slice();
+
+        for (idx, op) in ops.iter().enumerate() {
+            let condition = match idx.checked_sub(1).map(|idx| &ops[idx]) {
+                Some(Op::Inst(push)) => {
+                    // The span of a value pushed after the first one of a push instruction, e.g.
+                    // of the `1` in `push.0.1`, does not cover the whole instruction.
+                    let is_whole_push = source_file
+                        .source_slice(push.span().into_slice_index())
+                        .is_some_and(|source| source.starts_with("push"));
+                    pushed_value(push).filter(|_| is_whole_push).map(|value| (push.span(), value))
+                },
+                _ => None,
+            };
+
+            let (span, kept, suggestion) = match (op, condition) {
+                // `if.false` is parsed as `if.true` with swapped branches, so the `then` branch is
+                // always the one that is executed if the condition is `1`.
+                (Op::If { span, then_blk, .. }, Some((push, 1))) => (
+                    spanning(push, *span),
+                    Some(then_blk),
+                    "inline the branch that is always executed",
+                ),
+                (Op::If { span, else_blk, .. }, Some((push, 0))) => (
+                    spanning(push, *span),
+                    Some(else_blk),
+                    "inline the branch that is always executed",
+                ),
+                (Op::While { span, .. }, Some((push, 0))) => (
+                    spanning(push, *span),
+                    None,
+                    "remove the loop, since its body is never executed",
+                ),
+                (Op::Repeat { span, count: 1, body }, _) => {
+                    (*span, Some(body), "inline the body, since it is executed exactly once")
+                },
+                _ => continue,
+            };
+
+            early_ctx.push_error(LintErrorKind::ConstantCondition {
+                span,
+                suggestion,
+                replacement: replacement(kept, &source_file),
+                source_file: early_ctx.source_file(),
+            });
+        }
+    }
+}
+
+/// Returns the code that replaces the statement if its kept block can be inlined without changing
+/// the indentation, which is the case if it is empty or written on a single line.
+fn replacement(kept: Option<&Block>, source_file: &SourceFile) -> Option<String> {
+    let Some(kept) = kept.filter(|kept| !is_empty(kept)) else {
+        return Some(String::new());
+    };
+
+    let ops = kept.iter().as_slice();
+    let (first, last) = (ops.first()?.span(), ops.last()?.span());
+    let source = source_file.source_slice(spanning(first, last).into_slice_index())?;
+
+    (!source.contains('\n')).then(|| source.to_owned())
+}
+
+/// Returns the span from the start of `first` to the end of `last`.
+fn spanning(first: SourceSpan, last: SourceSpan) -> SourceSpan {
+    SourceSpan::new(first.source_id(), first.start()..last.end())
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_assembly::{DefaultSourceManager, SourceManager};
+
+    use super::*;
+    use crate::{Lint, Linter};
+
+    /// Lints the source with this lint and returns the replacements of the reported statements.
+    fn replacements(source: &str) -> Vec<Option<String>> {
+        let source_manager = DefaultSourceManager::default();
+        let source_file = source_manager.load("test.masm", source.to_owned());
+
+        let mut linter = Linter::new(vec![Lint::Early(Box::new(ConstantCondition))]);
+        linter.lint(source_file);
+        let errors = linter.finish().map(|_| Vec::new()).unwrap_or_else(|err| err.into_errors());
+
+        errors
+            .into_iter()
+            .map(|error| match error.kind() {
+                LintErrorKind::ConstantCondition { replacement, .. } => replacement.clone(),
+                kind => panic!("unexpected error: {kind:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn statically_known_conditions_are_reported() {
+        let source = "\
+proc.foo
+    push.1 if.true add else mul end
+    push.0 if.true add else mul end
+    push.0 while.true add end
+    repeat.1 add end
+    push.1 if.true
+        add
+        mul
+    end
+end
+";
+        assert_eq!(
+            replacements(source),
+            [
+                Some("add".to_owned()),
+                Some("mul".to_owned()),
+                Some(String::new()),
+                Some("add".to_owned()),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn computed_conditions_are_not_reported() {
+        let source = "\
+proc.foo
+    dup if.true add else mul end
+    push.2 if.true add end
+    push.1 while.true push.0 end
+    push.0.1 if.true add end
+    repeat.2 add end
+end
+";
+        assert_eq!(replacements(source), Vec::<Option<String>>::new());
+    }
+}
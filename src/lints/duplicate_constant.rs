@@ -0,0 +1,146 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use miden_assembly::ast::ConstantExpr;
+
+use super::error_constant::error_constants;
+use crate::{LateContext, LateLintPass, LintErrorKind, LintInfo, Severity};
+
+/// Checks for constants that are defined with the same literal value as another constant of their
+/// module.
+///
+/// Two error constants with the same message make it impossible to tell which assertion failed.
+/// Constants that are defined in terms of another constant, e.g. `const.B=A`, are not compared,
+/// since they are equal on purpose. If `error_messages_only` is set, only constants that are used
+/// as the error message of an assertion are compared, since other constants, e.g. sizes or
+/// offsets, often share a value by coincidence.
+pub struct DuplicateConstant {
+    error_messages_only: bool,
+}
+
+impl DuplicateConstant {
+    pub const NAME: &'static str = "duplicate_constant";
+
+    pub fn new(error_messages_only: bool) -> Self {
+        Self { error_messages_only }
+    }
+}
+
+impl LateLintPass for DuplicateConstant {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks for constants with the same value as another constant.",
+            help: "Use a distinct value for each constant, or use the other constant instead.",
+            default_severity: Severity::Warning,
+        }
+    }
+
+    fn check_module_graph(&mut self, late_ctx: &mut LateContext) {
+        let mut errors = Vec::new();
+
+        for module in late_ctx.graph().modules() {
+            let mut first_definitions: BTreeMap<LiteralValue, &str> = BTreeMap::new();
+
+            let constants: Vec<_> = if self.error_messages_only {
+                error_constants(module).collect()
+            } else {
+                module.constants().collect()
+            };
+
+            for constant in constants {
+                let Some(value) = LiteralValue::of(&constant.value) else {
+                    continue;
+                };
+
+                match first_definitions.get(&value) {
+                    Some(original) => errors.push(LintErrorKind::DuplicateConstant {
+                        span: constant.span,
+                        name: constant.name.to_string(),
+                        original: original.to_string(),
+                        source_file: Arc::clone(module.source_file()),
+                    }),
+                    None => {
+                        first_definitions.insert(value, constant.name.as_str());
+                    },
+                }
+            }
+        }
+
+        for error in errors {
+            late_ctx.push_error(error);
+        }
+    }
+}
+
+/// The value of a constant that is defined by a literal.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum LiteralValue<'a> {
+    Felt(u64),
+    String(&'a str),
+}
+
+impl<'a> LiteralValue<'a> {
+    fn of(expr: &'a ConstantExpr) -> Option<Self> {
+        match expr {
+            ConstantExpr::Literal(value) => Some(Self::Felt(value.inner().as_int())),
+            ConstantExpr::String(value) => Some(Self::String(value.as_str())),
+            ConstantExpr::Var(_) | ConstantExpr::BinaryOp { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_assembly::{DefaultSourceManager, SourceManager};
+
+    use super::*;
+    use crate::{Lint, Linter};
+
+    /// Lints the source with this lint and returns the names of the reported constants.
+    fn reported_constants(source: &str, error_messages_only: bool) -> Vec<String> {
+        let source_manager = DefaultSourceManager::default();
+        let source_file = source_manager.load("test.masm", source.to_owned());
+
+        let lint = DuplicateConstant::new(error_messages_only);
+        let mut linter = Linter::new(vec![Lint::Late(Box::new(lint))]);
+        linter.lint(source_file);
+        let errors = linter.finish().map(|_| Vec::new()).unwrap_or_else(|err| err.into_errors());
+
+        errors
+            .into_iter()
+            .map(|error| match error.kind() {
+                LintErrorKind::DuplicateConstant { name, .. } => name.clone(),
+                kind => panic!("unexpected error: {kind:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn numeric_constants_with_the_same_value_are_reported() {
+        let source = "const.A=1\nconst.B=2\nconst.C=0x01\n";
+        assert_eq!(reported_constants(source, false), ["C"]);
+    }
+
+    #[test]
+    fn constants_defined_by_another_constant_are_not_reported() {
+        let source = "const.A=1\nconst.B=A\n";
+        assert_eq!(reported_constants(source, false), Vec::<String>::new());
+    }
+
+    #[test]
+    fn only_error_constants_are_compared_if_configured() {
+        let source = "\
+const.A=1
+const.B=1
+const.ERR_A=\"value is zero\"
+const.ERR_B=\"value is zero\"
+
+proc.foo
+    assert.err=ERR_A
+    assert.err=ERR_B
+end
+";
+        assert_eq!(reported_constants(source, false), ["B", "ERR_B"]);
+        assert_eq!(reported_constants(source, true), ["ERR_B"]);
+    }
+}
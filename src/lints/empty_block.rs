@@ -0,0 +1,210 @@
+use miden_assembly::{
+    SourceSpan, Spanned,
+    ast::{Block, Procedure},
+};
+
+use super::manual_assert::is_empty;
+use crate::{Container, EarlyContext, EarlyLintPass, LintErrorKind, LintInfo, Severity};
+
+/// Checks for blocks that contain only `nop` instructions, such as empty branches of an `if`, the
+/// bodies of loops and the bodies of procedures.
+///
+/// The `else` branch that the parser inserts for an omitted `else` is not reported.
+pub struct EmptyBlock;
+
+impl EmptyBlock {
+    pub const NAME: &'static str = "empty_block";
+}
+
+impl EarlyLintPass for EmptyBlock {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks for branches, loop bodies and procedure bodies that only contain \
+                          `nop`.",
+            help: "Remove the empty block, e.g. the `else nop` of an `if`, or the statement or \
+                   procedure that contains it.",
+            default_severity: Severity::Warning,
+        }
+    }
+
+    fn enter_procedure(&mut self, early_ctx: &mut EarlyContext, procedure: &Procedure) {
+        let Some(span) = nop_span(procedure.body()) else {
+            return;
+        };
+
+        let block = match early_ctx.container() {
+            Some(Container::Entrypoint) => "body of the program",
+            _ => "body of the procedure",
+        };
+        early_ctx.push_error(LintErrorKind::EmptyBlock {
+            span,
+            block,
+            suggestion: "remove the `nop` or implement the body",
+            source_file: early_ctx.source_file(),
+        });
+    }
+
+    fn check_if(
+        &mut self,
+        early_ctx: &mut EarlyContext,
+        span: SourceSpan,
+        then_blk: &Block,
+        else_blk: &Block,
+    ) {
+        // `if.false` is parsed with swapped branches, so the branches are ordered by their
+        // position in the source. The parser inserts a block with a single `nop` that has the span
+        // of the whole `if` for an omitted `else`, which therefore comes first.
+        let (mut first, mut second) = (then_blk, else_blk);
+        if start(second) < start(first) {
+            std::mem::swap(&mut first, &mut second);
+        }
+        let is_omitted = |block: &Block| nop_span(block) == Some(span);
+
+        let (span, block, suggestion) = match (nop_span(first), nop_span(second)) {
+            (Some(_), Some(_)) => (
+                span,
+                "`if` statement",
+                "replace the `if` with `drop`, which removes the condition from the stack",
+            ),
+            (Some(_), None) if is_omitted(first) => return,
+            (Some(span), None) => (
+                span,
+                "first branch of the `if`",
+                "negate the condition of the `if` and remove the empty `else` branch",
+            ),
+            (None, Some(span)) => (span, "`else` branch", "remove the empty `else` branch"),
+            (None, None) => return,
+        };
+
+        early_ctx.push_error(LintErrorKind::EmptyBlock {
+            span,
+            block,
+            suggestion,
+            source_file: early_ctx.source_file(),
+        });
+    }
+
+    fn check_while(&mut self, early_ctx: &mut EarlyContext, span: SourceSpan, body: &Block) {
+        if nop_span(body).is_none() {
+            return;
+        }
+
+        early_ctx.push_error(LintErrorKind::EmptyBlock {
+            span,
+            block: "body of the `while` loop",
+            suggestion: "remove the loop, whose body does not push the condition of the next \
+                         iteration",
+            source_file: early_ctx.source_file(),
+        });
+    }
+
+    fn check_repeat(
+        &mut self,
+        early_ctx: &mut EarlyContext,
+        span: SourceSpan,
+        _count: u32,
+        body: &Block,
+    ) {
+        if nop_span(body).is_none() {
+            return;
+        }
+
+        early_ctx.push_error(LintErrorKind::EmptyBlock {
+            span,
+            block: "body of the `repeat` loop",
+            suggestion: "remove the loop",
+            source_file: early_ctx.source_file(),
+        });
+    }
+}
+
+/// Returns the span of the instructions of the block if it only contains `nop` instructions.
+fn nop_span(block: &Block) -> Option<SourceSpan> {
+    if !is_empty(block) {
+        return None;
+    }
+
+    let ops = block.iter().as_slice();
+
+    let (first, last) = (ops.first()?.span(), ops.last()?.span());
+    Some(SourceSpan::new(first.source_id(), first.start()..last.end()))
+}
+
+/// Returns the start of the first operation of the block.
+fn start(block: &Block) -> Option<impl Ord> {
+    block.iter().next().map(|op| op.span().start())
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_assembly::{DefaultSourceManager, SourceManager};
+
+    use super::*;
+    use crate::{Lint, Linter};
+
+    /// Lints the source with this lint and returns the descriptions of the reported blocks.
+    fn reported_blocks(source: &str) -> Vec<&'static str> {
+        let source_manager = DefaultSourceManager::default();
+        let source_file = source_manager.load("test.masm", source.to_owned());
+
+        let mut linter = Linter::new(vec![Lint::Early(Box::new(EmptyBlock))]);
+        linter.lint(source_file);
+        let errors = linter.finish().map(|_| Vec::new()).unwrap_or_else(|err| err.into_errors());
+
+        errors
+            .into_iter()
+            .map(|error| match error.kind() {
+                LintErrorKind::EmptyBlock { block, .. } => *block,
+                kind => panic!("unexpected error: {kind:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn blocks_with_only_nop_are_reported() {
+        let source = "\
+proc.foo
+    nop
+end
+
+proc.bar
+    if.true nop end
+    if.true add else nop end
+    if.false nop else add end
+    while.true nop end
+    repeat.2 nop nop end
+end
+
+begin
+    nop
+end
+";
+        assert_eq!(
+            reported_blocks(source),
+            [
+                "body of the procedure",
+                "`if` statement",
+                "`else` branch",
+                "first branch of the `if`",
+                "body of the `while` loop",
+                "body of the `repeat` loop",
+                "body of the program",
+            ]
+        );
+    }
+
+    #[test]
+    fn blocks_with_code_are_not_reported() {
+        let source = "\
+proc.foo
+    if.true add end
+    if.false add end
+    if.true add else mul end
+    while.true nop push.0 end
+    repeat.2 nop add end
+end
+";
+        assert_eq!(reported_blocks(source), Vec::<&str>::new());
+    }
+}
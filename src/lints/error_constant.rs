@@ -0,0 +1,165 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use glob::{Pattern, PatternError};
+use miden_assembly::{
+    LibraryPath,
+    ast::{Constant, ConstantExpr},
+};
+use miette::{Report, Result};
+
+use crate::{LateContext, LateLintPass, LintErrorKind, LintInfo, ParsedModule, Severity};
+
+/// Checks that the constants used as error messages of assertions follow the naming pattern of the
+/// project and that an error constant has the same message in all modules that define it.
+///
+/// The naming pattern is a glob, e.g. `ERR_*`, in which `<MODULE>` is replaced by the upper-case
+/// name of the module that defines the constant, e.g. `ERR_<MODULE>_*` requires the error
+/// constants of the `account` module to start with `ERR_ACCOUNT_`.
+pub struct ErrorConstant {
+    pattern: String,
+}
+
+impl ErrorConstant {
+    pub const NAME: &'static str = "error_constant";
+
+    /// The naming pattern of error constants if none is configured.
+    pub const DEFAULT_PATTERN: &'static str = "ERR_*";
+
+    /// The placeholder in the naming pattern that is replaced by the name of the module.
+    const MODULE_PLACEHOLDER: &'static str = "<MODULE>";
+
+    /// Creates the lint with the given naming pattern.
+    ///
+    /// Returns an error if the pattern is not a valid glob.
+    pub fn new(pattern: String) -> Result<Self> {
+        Pattern::new(&pattern.replace(Self::MODULE_PLACEHOLDER, "MODULE")).map_err(|err| {
+            Report::msg(format!("invalid error constant pattern `{pattern}`: {err}"))
+        })?;
+
+        Ok(Self { pattern })
+    }
+
+    /// Returns the naming pattern for the error constants of the module, as it is shown to users.
+    fn module_pattern(&self, module_path: &LibraryPath) -> String {
+        let module_name = module_path.last().to_uppercase();
+        self.pattern.replace(Self::MODULE_PLACEHOLDER, &module_name)
+    }
+
+    /// Returns the glob that matches the names of the error constants of the module.
+    ///
+    /// The module name is escaped, so that characters like `*` or `[` in it are matched literally.
+    /// Returns an error if the pattern is not a valid glob after the name is inserted, e.g. because
+    /// the placeholder is part of a character class.
+    fn module_glob(&self, module_path: &LibraryPath) -> Result<Pattern, PatternError> {
+        let module_name = Pattern::escape(&module_path.last().to_uppercase());
+        Pattern::new(&self.pattern.replace(Self::MODULE_PLACEHOLDER, &module_name))
+    }
+}
+
+impl Default for ErrorConstant {
+    fn default() -> Self {
+        Self {
+            pattern: Self::DEFAULT_PATTERN.to_owned(),
+        }
+    }
+}
+
+impl LateLintPass for ErrorConstant {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks that error constants follow the naming pattern and have the same \
+                          message in all modules.",
+            help: "Rename the constant to match the pattern, and use the same message for all \
+                   definitions of an error constant.",
+            default_severity: Severity::Warning,
+        }
+    }
+
+    fn check_module_graph(&mut self, late_ctx: &mut LateContext) {
+        let mut errors = Vec::new();
+        // The first definition of each error constant with a literal message, by name.
+        let mut messages: BTreeMap<&str, (&str, &LibraryPath)> = BTreeMap::new();
+
+        for module in late_ctx.graph().modules() {
+            let pattern = self.module_pattern(module.path());
+            let glob = self.module_glob(module.path());
+            if let (Err(err), Some(constant)) = (&glob, error_constants(module).next()) {
+                errors.push(LintErrorKind::InvalidErrorConstantPattern {
+                    span: constant.span,
+                    pattern: self.pattern.clone(),
+                    module: module.path().to_string(),
+                    reason: err.to_string(),
+                    source_file: Arc::clone(module.source_file()),
+                });
+            }
+
+            for constant in error_constants(module) {
+                let name = constant.name.as_str();
+
+                if let Ok(glob) = &glob
+                    && !glob.matches(name)
+                {
+                    errors.push(LintErrorKind::ErrorConstantName {
+                        span: constant.span,
+                        name: name.to_owned(),
+                        pattern: pattern.clone(),
+                        source_file: Arc::clone(module.source_file()),
+                    });
+                }
+
+                let ConstantExpr::String(message) = &constant.value else {
+                    continue;
+                };
+                match messages.get(name) {
+                    Some((first_message, first_module)) if *first_message != message.as_str() => {
+                        errors.push(LintErrorKind::InconsistentErrorConstant {
+                            span: constant.span,
+                            name: name.to_owned(),
+                            other_module: first_module.to_string(),
+                            other_message: first_message.to_string(),
+                            source_file: Arc::clone(module.source_file()),
+                        });
+                    },
+                    Some(_) => {},
+                    None => {
+                        messages.insert(name, (message.as_str(), module.path()));
+                    },
+                }
+            }
+        }
+
+        for error in errors {
+            late_ctx.push_error(error);
+        }
+    }
+}
+
+/// Returns the constants of the module that are used as the error message of an assertion.
+pub(super) fn error_constants(module: &ParsedModule) -> impl Iterator<Item = &Constant> {
+    let references = module.error_message_references();
+    module.constants().filter(move |constant| references.contains(&constant.name))
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_assembly::LibraryNamespace;
+
+    use super::*;
+
+    fn module_path(name: &str) -> LibraryPath {
+        LibraryPath::new_from_components(LibraryNamespace::Anon, []).append_unchecked(name)
+    }
+
+    #[test]
+    fn module_names_are_matched_literally() {
+        let lint = ErrorConstant::new("ERR_<MODULE>_*".to_owned()).unwrap();
+
+        let glob = lint.module_glob(&module_path("we[ird")).unwrap();
+        assert!(glob.matches("ERR_WE[IRD_X"));
+
+        let glob = lint.module_glob(&module_path("a?b")).unwrap();
+        assert!(glob.matches("ERR_A?B_X"));
+        assert!(!glob.matches("ERR_AXB_X"));
+    }
+}
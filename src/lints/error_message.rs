@@ -0,0 +1,107 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use miden_assembly::{
+    SourceSpan, Span,
+    ast::{Immediate, Instruction},
+};
+
+use super::bare_assert::EXAMPLE_ERROR_MESSAGE;
+use crate::{EarlyContext, EarlyLintPass, LintErrorKind, LintInfo, ParsedModule, Severity};
+
+/// Messages that are placeholders for a real error message, compared case-insensitively.
+const PLACEHOLDER_MESSAGES: &[&str] = &[EXAMPLE_ERROR_MESSAGE, "todo", "fixme"];
+
+/// Checks that the error messages of assertions describe the failure.
+///
+/// Empty and placeholder messages are reported, as well as literal messages that are used by more
+/// than one assertion of a module, since the failing assertion cannot be told apart from the
+/// message. If `require_constants` is set, literal messages are reported as well, so all messages
+/// are defined as named constants.
+pub struct ErrorMessage {
+    require_constants: bool,
+    /// The literal messages of the current module and the first assertion that uses them.
+    messages: BTreeMap<String, SourceSpan>,
+}
+
+impl ErrorMessage {
+    pub const NAME: &'static str = "error_message";
+
+    pub fn new(require_constants: bool) -> Self {
+        Self {
+            require_constants,
+            messages: BTreeMap::new(),
+        }
+    }
+
+    fn check_message(&mut self, span: SourceSpan, message: &str) -> Option<&'static str> {
+        let trimmed = message.trim();
+        if trimmed.is_empty() {
+            return Some("error message is empty");
+        }
+        if PLACEHOLDER_MESSAGES
+            .iter()
+            .any(|placeholder| trimmed.eq_ignore_ascii_case(placeholder))
+        {
+            return Some("error message is a placeholder");
+        }
+        if self.messages.entry(trimmed.to_owned()).or_insert(span) != &span {
+            return Some("error message is also used by another assertion");
+        }
+        if self.require_constants {
+            return Some("error message is not a named constant");
+        }
+
+        None
+    }
+}
+
+impl Default for ErrorMessage {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl EarlyLintPass for ErrorMessage {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks that the error messages of assertions are unique and not empty \
+                          or placeholders.",
+            help: "Describe the condition that failed in a message that is unique to the \
+                   assertion.",
+            default_severity: Severity::Warning,
+        }
+    }
+
+    fn check_module(&mut self, _early_ctx: &mut EarlyContext, _module: &ParsedModule) {
+        self.messages.clear();
+    }
+
+    fn lint_instruction(&mut self, early_ctx: &mut EarlyContext, instruction: &Span<Instruction>) {
+        let Some(Immediate::Value(message)) = match_error_message(instruction) else {
+            return;
+        };
+
+        if let Some(reason) = self.check_message(instruction.span(), message.inner()) {
+            early_ctx.push_error(LintErrorKind::ErrorMessage {
+                span: instruction.span(),
+                reason,
+                source_file: early_ctx.source_file(),
+            });
+        }
+    }
+}
+
+fn match_error_message(instruction: &Span<Instruction>) -> Option<&Immediate<Arc<str>>> {
+    match instruction.inner() {
+        Instruction::AssertWithError(message)
+        | Instruction::AssertEqWithError(message)
+        | Instruction::AssertEqwWithError(message)
+        | Instruction::AssertzWithError(message)
+        | Instruction::U32AssertWithError(message)
+        | Instruction::U32Assert2WithError(message)
+        | Instruction::U32AssertWWithError(message)
+        | Instruction::MTreeVerifyWithError(message) => Some(message),
+        _ => None,
+    }
+}
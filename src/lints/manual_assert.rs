@@ -0,0 +1,165 @@
+use miden_assembly::{
+    SourceSpan,
+    ast::{Block, Immediate, Instruction, Op},
+};
+
+use crate::{EarlyContext, EarlyLintPass, LintErrorKind, LintInfo, Severity};
+
+/// Checks for `if` statements with a branch that only fails unconditionally, e.g.
+/// `if.true push.0 assert end`, which can be written as an assertion of the condition.
+pub struct ManualAssert;
+
+impl ManualAssert {
+    pub const NAME: &'static str = "manual_assert";
+}
+
+impl EarlyLintPass for ManualAssert {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks for `if` statements with a branch that only fails, which can be \
+                          written as an assertion.",
+            help: "Assert the condition directly, e.g. `assertz` instead of \
+                   `if.true push.0 assert end`.",
+            default_severity: Severity::Warning,
+        }
+    }
+
+    fn check_if(
+        &mut self,
+        early_ctx: &mut EarlyContext,
+        span: SourceSpan,
+        then_blk: &Block,
+        else_blk: &Block,
+    ) {
+        // `if.false` is parsed as `if.true` with swapped branches, so the `then` branch is always
+        // the one that is executed if the condition is `1`.
+        let (assertion, failure, other_branch) = if let Some(failure) = match_failure(then_blk) {
+            ("assertz", failure, else_blk)
+        } else if let Some(failure) = match_failure(else_blk) {
+            ("assert", failure, then_blk)
+        } else {
+            return;
+        };
+
+        let source_file = early_ctx.source_file();
+        // Keep the error message of the failing assertion, e.g. `.err="message"`.
+        let Some(failure) = source_file.source_slice(failure.into_slice_index()) else {
+            return;
+        };
+        let error_message = failure.find('.').map(|idx| &failure[idx..]).unwrap_or_default();
+
+        early_ctx.push_error(LintErrorKind::ManualAssert {
+            span,
+            alternative: format!("{assertion}{error_message}"),
+            keeps_other_branch: !is_empty(other_branch),
+            source_file,
+        });
+    }
+}
+
+/// Returns the span of the assertion if the block consists only of an assertion that always fails,
+/// such as `push.0 assert` or `push.1 assertz`.
+fn match_failure(block: &Block) -> Option<SourceSpan> {
+    let [Op::Inst(push), Op::Inst(assertion)] = block.iter().as_slice() else {
+        return None;
+    };
+    always_fails(push, assertion).then(|| assertion.span())
+}
+
+/// Returns `true` if the assertion always fails because of the value pushed right before it, such
+/// as in `push.0 assert` or `push.1 assertz`.
+pub(super) fn always_fails(push: &Instruction, assertion: &Instruction) -> bool {
+    let Some(pushed) = pushed_value(push) else {
+        return false;
+    };
+
+    match assertion {
+        Instruction::Assert | Instruction::AssertWithError(_) => pushed == 0,
+        Instruction::Assertz | Instruction::AssertzWithError(_) => pushed != 0,
+        _ => false,
+    }
+}
+
+/// Returns the value pushed by the instruction if it pushes a single literal value.
+pub(super) fn pushed_value(instruction: &Instruction) -> Option<u64> {
+    match instruction {
+        Instruction::Push(Immediate::Value(value)) => Some(value.inner().as_int()),
+        Instruction::PushU8(value) => Some((*value).into()),
+        Instruction::PushU16(value) => Some((*value).into()),
+        Instruction::PushU32(value) => Some((*value).into()),
+        Instruction::PushFelt(value) => Some(value.as_int()),
+        _ => None,
+    }
+}
+
+/// Returns `true` if the block does nothing, which includes the block the parser inserts for an
+/// omitted `else` branch.
+pub(super) fn is_empty(block: &Block) -> bool {
+    block.iter().all(
+        |op| matches!(op, Op::Inst(instruction) if matches!(instruction.inner(), Instruction::Nop)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_assembly::{DefaultSourceManager, SourceManager};
+
+    use super::*;
+    use crate::{Lint, Linter};
+
+    /// Lints the source with this lint and returns the suggested assertions and whether the other
+    /// branch is kept.
+    fn alternatives(source: &str) -> Vec<(String, bool)> {
+        let source_manager = DefaultSourceManager::default();
+        let source_file = source_manager.load("test.masm", source.to_owned());
+
+        let mut linter = Linter::new(vec![Lint::Early(Box::new(ManualAssert))]);
+        linter.lint(source_file);
+        let errors = linter.finish().map(|_| Vec::new()).unwrap_or_else(|err| err.into_errors());
+
+        errors
+            .into_iter()
+            .map(|error| match error.kind() {
+                LintErrorKind::ManualAssert { alternative, keeps_other_branch, .. } => {
+                    (alternative.clone(), *keeps_other_branch)
+                },
+                kind => panic!("unexpected error: {kind:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn branches_that_always_fail_are_reported() {
+        let source = "\
+proc.foo
+    if.true push.0 assert end
+    if.false push.0 assert end
+    if.true add else push.1 assertz.err=\"bad\" end
+end
+";
+        assert_eq!(
+            alternatives(source),
+            [
+                ("assertz".to_owned(), false),
+                ("assert".to_owned(), false),
+                ("assert.err=\"bad\"".to_owned(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn branches_that_may_succeed_are_not_reported() {
+        let source = "\
+const.ZERO=0
+
+proc.foo
+    if.true push.1 assert end
+    if.true push.0 assert add end
+    if.true push.ZERO assert end
+    push.0 assert
+end
+";
+        assert_eq!(alternatives(source), Vec::<(String, bool)>::new());
+    }
+}
@@ -0,0 +1,88 @@
+use miden_assembly::{SourceSpan, Span, ast::Instruction};
+
+use crate::{EarlyContext, EarlyLintPass, LintErrorKind, LintInfo, Severity};
+
+/// Checks for lines with more instructions than the configured maximum.
+///
+/// Instructions in nested blocks on the same line, e.g. in `if.true add else sub end`, are counted
+/// for the line as well.
+pub struct MaxInstructionsPerLine {
+    max: usize,
+    line: Option<LineInstructions>,
+}
+
+/// The instructions on the line of the last linted instruction.
+struct LineInstructions {
+    /// The span of the first instruction on the line.
+    first: SourceSpan,
+    /// The span of the last instruction on the line.
+    last: SourceSpan,
+    count: usize,
+}
+
+impl MaxInstructionsPerLine {
+    pub const NAME: &'static str = "max_instructions_per_line";
+
+    /// The maximum number of instructions per line if none is configured.
+    pub const DEFAULT_MAX: usize = 8;
+
+    pub fn new(max: usize) -> Self {
+        Self { max, line: None }
+    }
+}
+
+impl Default for MaxInstructionsPerLine {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX)
+    }
+}
+
+impl EarlyLintPass for MaxInstructionsPerLine {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks for lines with more instructions than the configured maximum.",
+            help: "Split the line into multiple lines, e.g. one per step of the computation.",
+            default_severity: Severity::Warning,
+        }
+    }
+
+    fn lint_instruction(&mut self, early_ctx: &mut EarlyContext, instruction: &Span<Instruction>) {
+        let span = instruction.span();
+        let source_file = early_ctx.source_file();
+        // Instructions that the parser derives from the same code, such as `push.2 lt` from
+        // `lt.2`, share its span and are counted once. The `nop` the parser inserts for an omitted
+        // `else` block has the span of the whole `if` and is not counted.
+        let is_inserted_nop = matches!(instruction.inner(), Instruction::Nop)
+            && !source_file
+                .source_slice(span.into_slice_index())
+                .is_some_and(|source| source.starts_with("nop"));
+        if is_inserted_nop || self.line.as_ref().is_some_and(|line| line.last == span) {
+            return;
+        }
+
+        let content = source_file.content();
+        let is_same_line = self.line.as_ref().is_some_and(|line| {
+            line.first.source_id() == span.source_id()
+                && content.line_index(line.first.start()) == content.line_index(span.start())
+        });
+
+        let line = match self.line.as_mut() {
+            Some(line) if is_same_line => {
+                line.last = span;
+                line.count += 1;
+                line
+            },
+            _ => self.line.insert(LineInstructions { first: span, last: span, count: 1 }),
+        };
+
+        // Report the line only once, when the first instruction above the maximum is found.
+        if line.count == self.max + 1 {
+            early_ctx.push_error(LintErrorKind::MaxInstructionsPerLine {
+                span: SourceSpan::new(span.source_id(), line.first.start()..line.last.end()),
+                max: self.max,
+                source_file,
+            });
+        }
+    }
+}
@@ -3,3 +3,51 @@ pub use push_immediate::PushImmediate;
 
 mod bare_assert;
 pub use bare_assert::BareAssert;
+
+mod stack_comment_drift;
+pub use stack_comment_drift::StackCommentDrift;
+
+mod negated_comparison;
+pub use negated_comparison::NegatedComparison;
+
+mod manual_assert;
+pub use manual_assert::ManualAssert;
+
+mod max_instructions_per_line;
+pub use max_instructions_per_line::MaxInstructionsPerLine;
+
+mod redundant_shuffle;
+pub use redundant_shuffle::RedundantShuffle;
+
+mod verbose_shuffle;
+pub use verbose_shuffle::VerboseShuffle;
+
+mod unused_procedure;
+pub use unused_procedure::UnusedProcedure;
+
+mod unused_import;
+pub use unused_import::UnusedImport;
+
+mod unused_constant;
+pub use unused_constant::UnusedConstant;
+
+mod duplicate_constant;
+pub use duplicate_constant::DuplicateConstant;
+
+mod error_message;
+pub use error_message::ErrorMessage;
+
+mod error_constant;
+pub use error_constant::ErrorConstant;
+
+mod unreachable_code;
+pub use unreachable_code::UnreachableCode;
+
+mod constant_condition;
+pub use constant_condition::ConstantCondition;
+
+mod empty_block;
+pub use empty_block::EmptyBlock;
+
+mod unused_suppression;
+pub use unused_suppression::UnusedSuppression;
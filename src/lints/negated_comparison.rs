@@ -0,0 +1,128 @@
+use miden_assembly::ast::Block;
+
+use crate::{
+    EarlyContext, EarlyLintPass, LintErrorKind, LintInfo, Severity,
+    peephole::{self, Pattern},
+};
+
+/// The comparisons and the comparisons that produce their negated result.
+const NEGATED_COMPARISONS: &[(&str, &str)] = &[
+    ("eq", "neq"),
+    ("neq", "eq"),
+    ("lt", "gte"),
+    ("lte", "gt"),
+    ("gt", "lte"),
+    ("gte", "lt"),
+    ("u32lt", "u32gte"),
+    ("u32lte", "u32gt"),
+    ("u32gt", "u32lte"),
+    ("u32gte", "u32lt"),
+];
+
+/// Checks for comparisons whose result is negated with `not`, e.g. `eq not`, which can be written
+/// as the opposite comparison `neq`.
+pub struct NegatedComparison {
+    patterns: Vec<Pattern>,
+}
+
+impl NegatedComparison {
+    pub const NAME: &'static str = "negated_comparison";
+
+    pub fn new() -> Self {
+        let patterns = NEGATED_COMPARISONS
+            .iter()
+            .flat_map(|(comparison, negated)| {
+                [
+                    Pattern::new(&format!("{comparison} not"), negated),
+                    Pattern::new(&format!("{comparison}.$imm not"), &format!("{negated}.$imm")),
+                ]
+            })
+            .collect();
+
+        Self { patterns }
+    }
+}
+
+impl Default for NegatedComparison {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EarlyLintPass for NegatedComparison {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks for comparisons that are negated with `not` instead of using the \
+                          opposite comparison.",
+            help: "Use the opposite comparison, e.g. `neq` instead of `eq not`.",
+            default_severity: Severity::Warning,
+        }
+    }
+
+    fn enter_block(&mut self, early_ctx: &mut EarlyContext, block: &Block) {
+        let source_file = early_ctx.source_file();
+
+        for pattern_match in peephole::find_matches(&self.patterns, block, &source_file) {
+            early_ctx.push_error(LintErrorKind::NegatedComparison {
+                span: pattern_match.span,
+                alternative: pattern_match.replacement,
+                source_file: early_ctx.source_file(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_assembly::{DefaultSourceManager, SourceManager};
+
+    use super::*;
+    use crate::{Lint, Linter};
+
+    /// Lints the source with this lint and returns the suggested alternatives.
+    fn alternatives(source: &str) -> Vec<String> {
+        let source_manager = DefaultSourceManager::default();
+        let source_file = source_manager.load("test.masm", source.to_owned());
+
+        let mut linter = Linter::new(vec![Lint::Early(Box::new(NegatedComparison::new()))]);
+        linter.lint(source_file);
+        let errors = linter.finish().map(|_| Vec::new()).unwrap_or_else(|err| err.into_errors());
+
+        errors
+            .into_iter()
+            .map(|error| match error.kind() {
+                LintErrorKind::NegatedComparison { alternative, .. } => alternative.clone(),
+                kind => panic!("unexpected error: {kind:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn negated_comparisons_are_reported() {
+        let source = "\
+proc.foo
+    eq not
+    u32lt
+    not
+    gte.5 not
+end
+";
+        assert_eq!(alternatives(source), ["neq", "u32gte", "lt.5"]);
+    }
+
+    #[test]
+    fn other_negations_are_not_reported() {
+        let source = "\
+proc.foo
+    eq drop not
+    and not
+    if.true
+        eq
+    end
+    not
+end
+";
+        assert_eq!(alternatives(source), Vec::<String>::new());
+    }
+}
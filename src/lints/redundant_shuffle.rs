@@ -0,0 +1,119 @@
+use miden_assembly::ast::Block;
+
+use crate::{
+    EarlyContext, EarlyLintPass, LintErrorKind, LintInfo, Severity,
+    peephole::{self, Pattern},
+};
+
+/// Sequences of stack manipulations that leave the stack unchanged, in their canonical form, e.g.
+/// `swap.1` for `swap` and `dup.0` for `dup`.
+const REDUNDANT_SHUFFLES: &[&str] = &[
+    "swap.$n swap.$n",
+    "swapw.$n swapw.$n",
+    "swapdw swapdw",
+    "dup.$n drop",
+    "dupw.$n dropw",
+    "padw dropw",
+    "movup.$n movdn.$n",
+    "movdn.$n movup.$n",
+    "movupw.$n movdnw.$n",
+    "movdnw.$n movupw.$n",
+];
+
+/// Checks for consecutive stack manipulations that cancel each other out, e.g. `swap swap`.
+pub struct RedundantShuffle {
+    patterns: Vec<Pattern>,
+}
+
+impl RedundantShuffle {
+    pub const NAME: &'static str = "redundant_shuffle";
+
+    pub fn new() -> Self {
+        let patterns = REDUNDANT_SHUFFLES.iter().map(|pattern| Pattern::new(pattern, "")).collect();
+        Self { patterns }
+    }
+}
+
+impl Default for RedundantShuffle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EarlyLintPass for RedundantShuffle {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks for consecutive stack manipulations that cancel each other out, \
+                          such as `swap swap` or `dup drop`.",
+            help: "Remove the stack manipulations, since they leave the stack unchanged.",
+            default_severity: Severity::Warning,
+        }
+    }
+
+    fn enter_block(&mut self, early_ctx: &mut EarlyContext, block: &Block) {
+        let source_file = early_ctx.source_file();
+
+        for pattern_match in peephole::find_matches(&self.patterns, block, &source_file) {
+            early_ctx.push_error(LintErrorKind::RedundantShuffle {
+                span: pattern_match.span,
+                source_file: early_ctx.source_file(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_assembly::{DefaultSourceManager, SourceManager};
+
+    use super::*;
+    use crate::{Lint, Linter};
+
+    /// Lints the source with this lint and returns the code of the reported sequences.
+    fn reported_code(source: &str) -> Vec<String> {
+        let source_manager = DefaultSourceManager::default();
+        let source_file = source_manager.load("test.masm", source.to_owned());
+
+        let mut linter = Linter::new(vec![Lint::Early(Box::new(RedundantShuffle::new()))]);
+        linter.lint(source_file);
+        let errors = linter.finish().map(|_| Vec::new()).unwrap_or_else(|err| err.into_errors());
+
+        errors
+            .iter()
+            .map(|error| {
+                assert_eq!(error.lint_name(), RedundantShuffle::NAME);
+                source[error.span().into_slice_index()].to_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shuffles_that_cancel_out_are_reported() {
+        let source = "\
+proc.foo
+    swap swap
+    dup drop
+    movup.3 movdn.3
+    swap.1 swap
+end
+";
+        assert_eq!(
+            reported_code(source),
+            ["swap swap", "dup drop", "movup.3 movdn.3", "swap.1 swap"]
+        );
+    }
+
+    #[test]
+    fn shuffles_that_change_the_stack_are_not_reported() {
+        let source = "\
+proc.foo
+    movup.2 movdn.3
+    swap.2 swap.3
+    dup.1 dropw
+    swap add swap
+end
+";
+        assert_eq!(reported_code(source), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,573 @@
+use std::{ops::Range, sync::Arc};
+
+use miden_assembly::{
+    SourceFile, SourceSpan, Span, Spanned,
+    ast::{Block, Instruction, Op, Procedure},
+};
+
+use crate::{
+    EarlyContext, EarlyLintPass, LintErrorKind, LintInfo, ParsedModule, Severity,
+    stack_effect::{Shuffle, StackEffect},
+};
+
+/// Checks that `# => [...]` stack comments match the stack the code produces.
+///
+/// Each stack comment declares the state of the stack at its position. Starting from a comment,
+/// the lint tracks how the following instructions move the declared elements and checks the next
+/// comment in the same block against the result. Elements that are produced by instructions other
+/// than shuffles, e.g. the result of `add`, are unnamed and match any name, so renaming an element
+/// in a comment is not reported.
+pub struct StackCommentDrift {
+    /// The stack comments of the current source file, ordered by their position.
+    comments: Vec<StackComment>,
+    source_file: Option<Arc<SourceFile>>,
+    /// The spans of the blocks that enclose the current instruction, innermost last.
+    blocks: Vec<SourceSpan>,
+    /// The stack declared by the last comment, updated by the instructions after it, or `None` if
+    /// it is unknown.
+    stack: Option<TrackedStack>,
+    /// The offset up to which comments have been checked.
+    cursor: u32,
+}
+
+impl StackCommentDrift {
+    pub const NAME: &'static str = "stack_comment_drift";
+
+    pub fn new() -> Self {
+        Self {
+            comments: Vec::new(),
+            source_file: None,
+            blocks: Vec::new(),
+            stack: None,
+            cursor: 0,
+        }
+    }
+
+    /// Checks the comments before `offset` against the tracked stack and continues tracking from
+    /// the stack declared by the last of them.
+    fn check_comments(&mut self, early_ctx: &mut EarlyContext, offset: u32) {
+        let current_block = self.blocks.last().copied();
+        let start = self
+            .comments
+            .partition_point(|comment| comment.span.start().to_u32() < self.cursor);
+        // The offset can be before the cursor for instructions inserted by the parser, such as the
+        // `nop` of an omitted `else` block, whose span is the one of the whole `if`.
+        let end = self
+            .comments
+            .partition_point(|comment| comment.span.start().to_u32() < offset)
+            .max(start);
+        self.cursor = self.cursor.max(offset);
+
+        for comment in &self.comments[start..end] {
+            // A comment that is not in the current block precedes the keyword that started it, so
+            // it belongs to the enclosing block.
+            let block = match current_block {
+                Some(block) if self.is_in_block(comment, block) => Some(block),
+                _ => self.blocks.len().checked_sub(2).map(|parent| self.blocks[parent]),
+            };
+
+            if let Some(stack) = self.stack.take()
+                && Some(stack.block) == block
+                && let Some(reason) = stack.mismatch(&comment.elements)
+            {
+                early_ctx.push_error(LintErrorKind::StackCommentDrift {
+                    span: comment.span,
+                    reason,
+                    actual: stack.to_string(),
+                    source_file: early_ctx.source_file(),
+                });
+            }
+
+            self.stack = block.map(|block| TrackedStack::declared(block, &comment.elements));
+        }
+    }
+
+    /// Returns the offset of the keyword that ends the block, such as `end` or `else`, which
+    /// follows the last instruction of the block after any whitespace and comments.
+    fn block_end(&self, block: SourceSpan) -> u32 {
+        let Some(source_file) = self.source_file.as_ref() else {
+            return block.end().to_u32();
+        };
+
+        let source = source_file.as_str();
+        let mut offset = block.end().to_u32() as usize;
+        loop {
+            let rest = &source[offset..];
+            let trimmed = rest.trim_start();
+            offset += rest.len() - trimmed.len();
+            if !trimmed.starts_with('#') {
+                return offset as u32;
+            }
+            offset += trimmed.find('\n').unwrap_or(trimmed.len());
+        }
+    }
+
+    /// Returns `true` if the comment is inside the block, including before its first instruction.
+    ///
+    /// Comments before the keyword that starts the block, such as `if.true`, belong to the
+    /// enclosing block.
+    fn is_in_block(&self, comment: &StackComment, block: SourceSpan) -> bool {
+        let comment_end = comment.span.end().to_u32();
+        let block_start = block.start().to_u32();
+        if comment_end >= block_start {
+            return true;
+        }
+
+        let Some(source_file) = self.source_file.as_ref() else {
+            return false;
+        };
+        let between = &source_file.as_str()[comment_end as usize..block_start as usize];
+        between.lines().all(|line| {
+            let line = line.trim();
+            line.is_empty() || line.starts_with('#')
+        })
+    }
+}
+
+impl Default for StackCommentDrift {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EarlyLintPass for StackCommentDrift {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks that `# => [...]` stack comments match the stack produced by the \
+                          code.",
+            help: "Update the stack comment to match the stack the code produces, or fix the \
+                   code if the comment is correct.",
+            default_severity: Severity::Warning,
+        }
+    }
+
+    fn check_module(&mut self, _early_ctx: &mut EarlyContext, module: &ParsedModule) {
+        self.source_file = Some(Arc::clone(module.source_file()));
+        self.comments = parse_stack_comments(module.source_file());
+        self.blocks.clear();
+        self.stack = None;
+        self.cursor = 0;
+    }
+
+    /// The stack at the start of a procedure is unrelated to the one at the end of the previous
+    /// procedure, so it is unknown until the first comment of the procedure.
+    fn enter_procedure(&mut self, _early_ctx: &mut EarlyContext, _procedure: &Procedure) {
+        self.stack = None;
+    }
+
+    fn lint_instruction(&mut self, early_ctx: &mut EarlyContext, instruction: &Span<Instruction>) {
+        let span = instruction.span();
+        self.check_comments(early_ctx, span.start().to_u32());
+        self.cursor = self.cursor.max(span.end().to_u32());
+
+        let Some(stack) = self.stack.as_mut() else {
+            return;
+        };
+        if Some(stack.block) != self.blocks.last().copied() {
+            self.stack = None;
+            return;
+        }
+
+        let effect = early_ctx.stack_effects().instruction(instruction);
+        if stack.apply(instruction, effect).is_none() {
+            self.stack = None;
+        }
+    }
+
+    /// The stack in the nested block is unknown until the next comment, but the stack of the
+    /// enclosing block is kept until its comments before the nested block have been checked.
+    fn enter_block(&mut self, _early_ctx: &mut EarlyContext, block: &Block) {
+        self.blocks.push(block.span());
+    }
+
+    /// Checks the comments after the last instruction of the block, which belong to the block.
+    fn exit_block(&mut self, early_ctx: &mut EarlyContext, block: &Block) {
+        // The `nop` block the parser inserts for an omitted `else` has the span of the whole `if`
+        // and contains no comments of its own.
+        let is_inserted = match block.iter().as_slice() {
+            [Op::Inst(instruction)] if matches!(instruction.inner(), Instruction::Nop) => {
+                self.source_file.as_ref().is_some_and(|source_file| {
+                    source_file
+                        .source_slice(instruction.span().into_slice_index())
+                        .is_some_and(|source| source.starts_with("if"))
+                })
+            },
+            _ => false,
+        };
+        if !is_inserted {
+            let end = self.block_end(block.span());
+            self.check_comments(early_ctx, end);
+        }
+
+        self.blocks.pop();
+    }
+}
+
+/// A stack comment of the form `# => [a, b, C]`.
+struct StackComment {
+    span: SourceSpan,
+    elements: DeclaredStack,
+}
+
+/// The elements of a stack comment, top first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DeclaredStack {
+    elements: Vec<Element>,
+    /// Whether the comment ends with `...`, i.e. only declares the top of the stack.
+    has_rest: bool,
+    /// Whether the comment ends with padding, e.g. `pad(12)`, which marks it as declaring the
+    /// whole stack. Other comments may leave out elements at the bottom without `...`.
+    is_complete: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Element {
+    /// An element with a name, where all four elements of a word share the word's name.
+    Named(String),
+    /// An element whose name is not known, e.g. because it was computed by an instruction or
+    /// declared as padding.
+    Unnamed,
+}
+
+/// The stack declared by a comment, updated by the instructions that followed it.
+struct TrackedStack {
+    /// The span of the block in which the comment was declared.
+    block: SourceSpan,
+    stack: DeclaredStack,
+}
+
+impl TrackedStack {
+    fn declared(block: SourceSpan, stack: &DeclaredStack) -> Self {
+        Self { block, stack: stack.clone() }
+    }
+
+    /// Applies the instruction with the given effect to the stack.
+    ///
+    /// Returns `None` if the effect is unknown or if the instruction accesses elements below the
+    /// declared stack.
+    fn apply(&mut self, instruction: &Instruction, effect: Option<StackEffect>) -> Option<()> {
+        let elements = &mut self.stack.elements;
+
+        if let Some(shuffle) = Shuffle::of_instruction(instruction) {
+            if self.stack.has_rest && elements.len() < shuffle.depth() {
+                elements.resize(shuffle.depth(), Element::Unnamed);
+            }
+            return shuffle.apply(elements);
+        }
+
+        let effect = effect?;
+        if elements.len() < effect.inputs {
+            if !self.stack.has_rest {
+                return None;
+            }
+            elements.resize(effect.inputs, Element::Unnamed);
+        }
+
+        elements.splice(0..effect.inputs, vec![Element::Unnamed; effect.outputs]);
+        Some(())
+    }
+
+    /// Returns why the declared stack does not match the tracked stack, if it does not.
+    fn mismatch(&self, declared: &DeclaredStack) -> Option<String> {
+        let actual = &self.stack.elements;
+        let expected = &declared.elements;
+
+        // A comment may declare fewer elements than the stack has, unless it is complete and the
+        // tracked stack is exact.
+        let is_too_long = !declared.has_rest && expected.len() > actual.len();
+        let is_too_short =
+            !self.stack.has_rest && declared.is_complete && expected.len() < actual.len();
+        if is_too_long || is_too_short {
+            return Some(format!(
+                "comment declares {} element(s), but the stack has {}",
+                expected.len(),
+                actual.len()
+            ));
+        }
+
+        // Only report names that appear in the tracked stack at another position, since a name
+        // that does not appear in it at all is most likely a new name for a computed element.
+        actual.iter().zip(expected).enumerate().find_map(|(position, pair)| match pair {
+            (Element::Named(actual_name), Element::Named(expected_name))
+                if actual_name != expected_name
+                    && actual.contains(&Element::Named(expected_name.clone())) =>
+            {
+                Some(format!(
+                    "comment declares `{expected_name}` at position {position}, but the stack has \
+                     `{actual_name}` there"
+                ))
+            },
+            _ => None,
+        })
+    }
+}
+
+impl std::fmt::Display for TrackedStack {
+    /// Formats the stack like a stack comment, where unnamed elements are written as `_` and the
+    /// four elements of a word are written as the word's name.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names = Vec::new();
+        let mut elements = self.stack.elements.as_slice();
+
+        while let Some((element, rest)) = elements.split_first() {
+            match element {
+                Element::Named(name) if is_word_name(name) => {
+                    let word_len = elements.iter().take(4).take_while(|e| *e == element).count();
+                    names.push(name.as_str());
+                    elements = &elements[word_len..];
+                },
+                Element::Named(name) => {
+                    names.push(name.as_str());
+                    elements = rest;
+                },
+                Element::Unnamed => {
+                    names.push("_");
+                    elements = rest;
+                },
+            }
+        }
+
+        if self.stack.has_rest {
+            names.push("...");
+        }
+
+        write!(f, "[{}]", names.join(", "))
+    }
+}
+
+/// Parses all comments of the form `# => [...]` in the source file.
+fn parse_stack_comments(source_file: &SourceFile) -> Vec<StackComment> {
+    let source = source_file.as_str();
+    let mut comments = Vec::new();
+    let mut line_start = 0;
+
+    for line in source.split_inclusive('\n') {
+        if let Some((range, elements)) = parse_stack_comment(line) {
+            let range = line_start + range.start as u32..line_start + range.end as u32;
+            comments.push(StackComment {
+                span: SourceSpan::new(source_file.id(), range),
+                elements,
+            });
+        }
+        line_start += line.len() as u32;
+    }
+
+    comments
+}
+
+/// Parses the stack comment in the line, returning its byte range in the line and its elements.
+fn parse_stack_comment(line: &str) -> Option<(Range<usize>, DeclaredStack)> {
+    let comment_start = find_comment(line)?;
+    let comment = &line[comment_start + 1..];
+    // Doc comments start with `#!`.
+    if comment.starts_with('!') {
+        return None;
+    }
+
+    let content = comment.trim_start().strip_prefix("=>")?.trim();
+    let list = content.strip_prefix('[')?;
+    let list_end = list.find(']')?;
+    let range = comment_start..line.trim_end().len();
+
+    let mut stack = DeclaredStack {
+        elements: Vec::new(),
+        has_rest: false,
+        is_complete: false,
+    };
+    for item in list[..list_end].split(',').map(str::trim).filter(|item| !item.is_empty()) {
+        stack.is_complete = false;
+        if item == "..." {
+            stack.has_rest = true;
+        } else if let Some(count) = parse_padding(item) {
+            stack.is_complete = !stack.has_rest;
+            stack.elements.extend(std::iter::repeat_n(Element::Unnamed, count));
+        } else if is_word_name(item) {
+            stack.elements.extend(std::iter::repeat_n(Element::Named(item.to_owned()), 4));
+        } else if item.starts_with(|c: char| c.is_ascii_digit()) {
+            stack.elements.push(Element::Unnamed);
+        } else {
+            stack.elements.push(Element::Named(item.to_owned()));
+        }
+    }
+
+    Some((range, stack))
+}
+
+/// Returns the offset of the `#` that starts the comment of the line, ignoring `#` in strings such
+/// as `assert.err="#1"`.
+fn find_comment(line: &str) -> Option<usize> {
+    let mut in_string = false;
+    line.char_indices().find_map(|(offset, c)| {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return Some(offset),
+            _ => {},
+        }
+        None
+    })
+}
+
+/// Parses padding of the form `pad(n)`, returning the number of padding elements.
+fn parse_padding(item: &str) -> Option<usize> {
+    item.strip_prefix("pad(")?.strip_suffix(')')?.trim().parse().ok()
+}
+
+/// Returns `true` if the name is written in upper case, which by convention denotes a word.
+fn is_word_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_assembly::{DefaultSourceManager, SourceManager};
+
+    use super::*;
+    use crate::{Lint, Linter};
+
+    /// Lints the source with this lint and returns the lines of the reported comments.
+    fn reported_lines(source: &str) -> Vec<usize> {
+        let source_manager = DefaultSourceManager::default();
+        let source_file = source_manager.load("test.masm", source.to_owned());
+
+        let mut linter = Linter::new(vec![Lint::Early(Box::new(StackCommentDrift::new()))]);
+        linter.lint(source_file);
+        let errors = linter.finish().map(|_| Vec::new()).unwrap_or_else(|err| err.into_errors());
+
+        errors
+            .iter()
+            .map(|error| {
+                assert_eq!(error.lint_name(), StackCommentDrift::NAME);
+                let offset = error.span().start().to_usize();
+                source[..offset].matches('\n').count() + 1
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matching_comments_are_not_reported() {
+        let source = "\
+proc.foo
+    # => [a, b, c]
+    swap
+    # => [b, a, c]
+    drop
+    # => [a, c]
+end
+";
+        assert_eq!(reported_lines(source), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn last_comment_of_a_body_is_checked() {
+        let source = "\
+proc.bar
+    # => [a, b]
+    swap
+    # => [a, b, c]
+end
+";
+        assert_eq!(reported_lines(source), vec![4]);
+    }
+
+    #[test]
+    fn last_comment_of_a_nested_block_is_checked() {
+        let source = "\
+proc.bar
+    # => [a, b]
+    if.true
+        swap
+        # => [a, b]
+    end
+end
+";
+        // The stack at the start of the branch is unknown, so only the comment after the `if`
+        // could be checked, and there is none.
+        assert_eq!(reported_lines(source), Vec::<usize>::new());
+
+        let source = "\
+proc.bar
+    if.true
+        # => [a, b]
+        swap
+        # => [a, b]
+    end
+end
+";
+        assert_eq!(reported_lines(source), vec![5]);
+    }
+
+    #[test]
+    fn stack_is_not_carried_into_the_next_procedure() {
+        let source = "\
+proc.bar
+    # => [a, b]
+    drop
+    # => [b]
+end
+
+export.baz
+    # => [x, y]
+    if.true
+        drop
+    end
+end
+";
+        assert_eq!(reported_lines(source), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn hashes_in_strings_do_not_start_comments() {
+        let source = "\
+proc.foo
+    # => [a, b, c]
+    assert.err=\"# => [x]\" # => [b, c]
+    drop
+    # => [c]
+end
+";
+        assert_eq!(reported_lines(source), Vec::<usize>::new());
+
+        let source = source.replace("# => [b, c]\n", "# => [c, b]\n");
+        assert_eq!(reported_lines(&source), vec![3]);
+    }
+
+    #[test]
+    fn partial_comments_are_not_reported() {
+        let source = "\
+proc.foo
+    # => [a, b, c]
+    drop
+    # => [b]
+end
+";
+        assert_eq!(reported_lines(source), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn comments_with_more_elements_than_the_stack_are_reported() {
+        let source = "\
+proc.foo
+    # => [a, b]
+    drop
+    # => [b, c]
+end
+";
+        assert_eq!(reported_lines(source), vec![4]);
+    }
+
+    #[test]
+    fn complete_comments_with_fewer_elements_than_the_stack_are_reported() {
+        let source = "\
+proc.foo
+    # => [a, b, pad(2)]
+    drop
+    # => [b, pad(1)]
+    drop
+    # => [pad(1)]
+end
+";
+        assert_eq!(reported_lines(source), vec![4]);
+    }
+}
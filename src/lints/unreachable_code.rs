@@ -0,0 +1,108 @@
+use miden_assembly::{
+    SourceSpan, Spanned,
+    ast::{Block, Op},
+};
+
+use super::manual_assert;
+use crate::{EarlyContext, EarlyLintPass, LintErrorKind, LintInfo, Severity};
+
+/// Checks for instructions that follow an assertion that always fails in the same block, such as
+/// the `add` in `push.0 assert add`, which are never executed.
+pub struct UnreachableCode;
+
+impl UnreachableCode {
+    pub const NAME: &'static str = "unreachable_code";
+}
+
+impl EarlyLintPass for UnreachableCode {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks for code after an assertion that always fails, which is never \
+                          executed.",
+            help: "Remove the unreachable code, or the assertion if it is not meant to fail.",
+            default_severity: Severity::Warning,
+        }
+    }
+
+    fn enter_block(&mut self, early_ctx: &mut EarlyContext, block: &Block) {
+        let ops = block.iter().as_slice();
+
+        let failure = ops.windows(2).position(|pair| match pair {
+            [Op::Inst(push), Op::Inst(assertion)] => manual_assert::always_fails(push, assertion),
+            _ => false,
+        });
+        let Some(failure) = failure else {
+            return;
+        };
+
+        let (Some(first), Some(last)) = (ops.get(failure + 2), ops.last()) else {
+            return;
+        };
+
+        early_ctx.push_error(LintErrorKind::UnreachableCode {
+            span: SourceSpan::new(
+                first.span().source_id(),
+                first.span().start()..last.span().end(),
+            ),
+            failure: ops[failure + 1].span(),
+            source_file: early_ctx.source_file(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_assembly::{DefaultSourceManager, SourceManager};
+
+    use super::*;
+    use crate::{Lint, Linter};
+
+    /// Lints the source with this lint and returns the reported unreachable code.
+    fn reported_code(source: &str) -> Vec<String> {
+        let source_manager = DefaultSourceManager::default();
+        let source_file = source_manager.load("test.masm", source.to_owned());
+
+        let mut linter = Linter::new(vec![Lint::Early(Box::new(UnreachableCode))]);
+        linter.lint(source_file);
+        let errors = linter.finish().map(|_| Vec::new()).unwrap_or_else(|err| err.into_errors());
+
+        errors
+            .iter()
+            .map(|error| {
+                assert_eq!(error.lint_name(), UnreachableCode::NAME);
+                source[error.span().into_slice_index()].to_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn code_after_failing_assertions_is_reported() {
+        let source = "\
+proc.foo
+    push.0 assert add mul
+end
+
+proc.bar
+    if.true
+        push.1 assertz.err=\"unreachable\"
+        drop
+    end
+end
+";
+        assert_eq!(reported_code(source), ["add mul", "drop"]);
+    }
+
+    #[test]
+    fn code_after_assertions_that_may_succeed_is_not_reported() {
+        let source = "\
+proc.foo
+    push.1 assert add
+    push.0 assertz mul
+    dup assert drop
+    push.0 assert
+end
+";
+        assert_eq!(reported_code(source), Vec::<String>::new());
+    }
+}
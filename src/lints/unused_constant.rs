@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use crate::{LateContext, LateLintPass, LintErrorKind, LintInfo, Severity};
+
+/// Checks for constants that are not referenced by any instruction or other constant of their
+/// module.
+pub struct UnusedConstant;
+
+impl UnusedConstant {
+    pub const NAME: &'static str = "unused_constant";
+}
+
+impl LateLintPass for UnusedConstant {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks for constants that are never used.",
+            help: "Remove the constant, or use it where its value is needed.",
+            default_severity: Severity::Warning,
+        }
+    }
+
+    fn check_module_graph(&mut self, late_ctx: &mut LateContext) {
+        let mut errors = Vec::new();
+
+        for module in late_ctx.graph().modules() {
+            let references = module.constant_references();
+
+            for constant in module.constants() {
+                if references.contains(&constant.name) {
+                    continue;
+                }
+
+                errors.push(LintErrorKind::UnusedConstant {
+                    span: constant.span,
+                    name: constant.name.to_string(),
+                    source_file: Arc::clone(module.source_file()),
+                });
+            }
+        }
+
+        for error in errors {
+            late_ctx.push_error(error);
+        }
+    }
+}
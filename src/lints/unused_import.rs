@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use crate::{LateContext, LateLintPass, LintErrorKind, LintInfo, Severity};
+
+/// Checks for imports that are not used by any procedure or re-exported alias of their module.
+///
+/// Constants can only refer to other constants of the same module, so they never use an import.
+pub struct UnusedImport;
+
+impl UnusedImport {
+    pub const NAME: &'static str = "unused_import";
+}
+
+impl LateLintPass for UnusedImport {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks for imports that are never used.",
+            help: "Remove the import.",
+            default_severity: Severity::Warning,
+        }
+    }
+
+    fn check_module_graph(&mut self, late_ctx: &mut LateContext) {
+        let mut errors = Vec::new();
+
+        for module in late_ctx.graph().modules() {
+            // Semantic analysis counts the uses of each import by invocations such as
+            // `exec.u64::add` and by re-exports such as `export.u64::add`.
+            for import in module.imports().filter(|import| !import.is_used()) {
+                errors.push(LintErrorKind::UnusedImport {
+                    span: import.span,
+                    name: import.name.to_string(),
+                    path: import.path.to_string(),
+                    source_file: Arc::clone(module.source_file()),
+                });
+            }
+        }
+
+        for error in errors {
+            late_ctx.push_error(error);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::{collections::BTreeSet, sync::Arc};
+
+use miden_assembly::{
+    Spanned,
+    ast::{Export, ProcedureName},
+};
+
+use crate::{
+    LateContext, LateLintPass, LintErrorKind, LintInfo, ParsedModule, ResolvedTarget, Severity,
+    module_graph::invocation_targets,
+};
+
+/// Checks for private procedures that are not used by any exported procedure of their module.
+///
+/// A private procedure is used if it is invoked by an exported procedure or the entrypoint, either
+/// directly or through other private procedures. Procedures that are only invoked by unused
+/// procedures are therefore reported as well.
+pub struct UnusedProcedure;
+
+impl UnusedProcedure {
+    pub const NAME: &'static str = "unused_procedure";
+}
+
+impl LateLintPass for UnusedProcedure {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks for private procedures that are never used.",
+            help: "Remove the procedure, or invoke it where it is needed.",
+            default_severity: Severity::Warning,
+        }
+    }
+
+    fn check_module_graph(&mut self, late_ctx: &mut LateContext) {
+        let mut errors = Vec::new();
+
+        for module in late_ctx.graph().modules() {
+            let used = used_procedures(late_ctx, module);
+
+            for export in module.procedures() {
+                let Export::Procedure(procedure) = export else {
+                    continue;
+                };
+                if procedure.visibility().is_exported() || used.contains(procedure.name()) {
+                    continue;
+                }
+
+                errors.push(LintErrorKind::UnusedProcedure {
+                    span: procedure.name().span(),
+                    name: procedure.name().to_string(),
+                    source_file: Arc::clone(module.source_file()),
+                });
+            }
+        }
+
+        for error in errors {
+            late_ctx.push_error(error);
+        }
+    }
+}
+
+/// Returns the names of the procedures in the module that are reachable from its exported
+/// procedures.
+fn used_procedures(late_ctx: &LateContext, module: &ParsedModule) -> BTreeSet<ProcedureName> {
+    let mut used = BTreeSet::new();
+    let mut unvisited: Vec<_> = module
+        .procedures()
+        .filter(|export| export.visibility().is_exported())
+        .map(|export| export.name().clone())
+        .collect();
+
+    while let Some(name) = unvisited.pop() {
+        let Some(Export::Procedure(procedure)) = module.procedure(&name) else {
+            continue;
+        };
+
+        for target in invocation_targets(procedure) {
+            // Invocations by absolute path can refer to procedures in the same module as well.
+            if let ResolvedTarget::Procedure(target) = late_ctx.graph().resolve(module, &target)
+                && target.module == *module.path()
+                && used.insert(target.name.clone())
+            {
+                unvisited.push(target.name);
+            }
+        }
+    }
+
+    used
+}
@@ -0,0 +1,26 @@
+use crate::{LateContext, LateLintPass, LintInfo, Severity};
+
+/// Checks for suppression comments that do not suppress any finding, such as
+/// `# masmlint: allow(bare_assert)` before code without a bare assertion.
+///
+/// Whether a suppression is used is only known once all other lints have reported their findings,
+/// so the linter reports the unused suppressions itself after running all lints. This lint only
+/// makes them selectable and configurable like the findings of any other lint.
+pub struct UnusedSuppression;
+
+impl UnusedSuppression {
+    pub const NAME: &'static str = "unused_suppression";
+}
+
+impl LateLintPass for UnusedSuppression {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks for suppression comments that do not suppress any finding.",
+            help: "Remove the lint from the suppression comment.",
+            default_severity: Severity::Warning,
+        }
+    }
+
+    fn check_module_graph(&mut self, _late_ctx: &mut LateContext) {}
+}
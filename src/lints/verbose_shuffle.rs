@@ -0,0 +1,106 @@
+use miden_assembly::{
+    Span,
+    ast::{Block, Instruction},
+};
+
+use crate::{
+    EarlyContext, EarlyLintPass, LintErrorKind, LintInfo, Severity,
+    peephole::{self, Pattern},
+};
+
+/// Sequences of stack manipulations in their canonical form and the single instruction with the
+/// same effect.
+const VERBOSE_SHUFFLES: &[(&str, &str)] = &[
+    ("drop drop drop drop", "dropw"),
+    ("push.0 push.0 push.0 push.0", "padw"),
+    ("dup.3 dup.3 dup.3 dup.3", "dupw"),
+    ("dup.7 dup.7 dup.7 dup.7", "dupw.1"),
+    ("dup.11 dup.11 dup.11 dup.11", "dupw.2"),
+    ("dup.15 dup.15 dup.15 dup.15", "dupw.3"),
+    ("movup.2 movup.2", "movdn.2"),
+    ("movdn.2 movdn.2", "movup.2"),
+];
+
+/// Checks for stack manipulations that can be written with a single shorter instruction, e.g.
+/// `drop drop drop drop` instead of `dropw` or `swap.1` instead of `swap`.
+///
+/// `movup.1` and `movdn.1` are not checked, since the assembler rejects them as invalid.
+pub struct VerboseShuffle {
+    patterns: Vec<Pattern>,
+}
+
+impl VerboseShuffle {
+    pub const NAME: &'static str = "verbose_shuffle";
+
+    pub fn new() -> Self {
+        let patterns = VERBOSE_SHUFFLES
+            .iter()
+            .map(|(pattern, replacement)| Pattern::new(pattern, replacement))
+            .collect();
+
+        Self { patterns }
+    }
+}
+
+impl Default for VerboseShuffle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EarlyLintPass for VerboseShuffle {
+    fn info(&self) -> LintInfo {
+        LintInfo {
+            name: Self::NAME,
+            description: "Checks for stack manipulations that can be written with a single \
+                          shorter instruction.",
+            help: "Use the shorter instruction, e.g. `dropw` instead of `drop drop drop drop`.",
+            default_severity: Severity::Warning,
+        }
+    }
+
+    fn lint_instruction(&mut self, early_ctx: &mut EarlyContext, instruction: &Span<Instruction>) {
+        let Some(alternative) = match_default_immediate(instruction.inner()) else {
+            return;
+        };
+
+        // The default immediate is not visible in the instruction, since `swap` and `swap.1` are
+        // parsed into the same instruction.
+        let source_file = early_ctx.source_file();
+        let span = instruction.span();
+        if source_file
+            .source_slice(span.into_slice_index())
+            .is_some_and(|source| source != alternative)
+        {
+            early_ctx.push_error(LintErrorKind::VerboseShuffle {
+                span,
+                alternative: alternative.to_owned(),
+                source_file,
+            });
+        }
+    }
+
+    fn enter_block(&mut self, early_ctx: &mut EarlyContext, block: &Block) {
+        let source_file = early_ctx.source_file();
+
+        for pattern_match in peephole::find_matches(&self.patterns, block, &source_file) {
+            early_ctx.push_error(LintErrorKind::VerboseShuffle {
+                span: pattern_match.span,
+                alternative: pattern_match.replacement,
+                source_file: early_ctx.source_file(),
+            });
+        }
+    }
+}
+
+/// Returns the short form of instructions whose immediate can be omitted, e.g. `swap` for
+/// `swap.1`.
+fn match_default_immediate(instruction: &Instruction) -> Option<&'static str> {
+    match instruction {
+        Instruction::Swap1 => Some("swap"),
+        Instruction::SwapW1 => Some("swapw"),
+        Instruction::Dup0 => Some("dup"),
+        Instruction::DupW0 => Some("dupw"),
+        _ => None,
+    }
+}
@@ -0,0 +1,149 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::ControlFlow,
+};
+
+use miden_assembly::{
+    LibraryPath,
+    ast::{
+        AliasTarget, Export, InvocationTarget, Procedure, ProcedureName, QualifiedProcedureName,
+        visit::{self, Visit},
+    },
+};
+use miden_core::crypto::hash::RpoDigest;
+
+use crate::ParsedModule;
+
+/// The modules of a library, keyed by their fully-qualified path.
+///
+/// The graph is used by late lints to resolve imports and invocation targets across the modules
+/// of the linted library.
+#[derive(Debug, Default)]
+pub struct ModuleGraph {
+    modules: BTreeMap<LibraryPath, ParsedModule>,
+}
+
+impl ModuleGraph {
+    pub fn new(modules: impl IntoIterator<Item = ParsedModule>) -> Self {
+        Self {
+            modules: modules.into_iter().map(|module| (module.path().clone(), module)).collect(),
+        }
+    }
+
+    /// Returns all modules of the graph, ordered by path.
+    pub fn modules(&self) -> impl Iterator<Item = &ParsedModule> {
+        self.modules.values()
+    }
+
+    /// Returns the module with the given path, if it is part of the graph.
+    pub fn get(&self, path: &LibraryPath) -> Option<&ParsedModule> {
+        self.modules.get(path)
+    }
+
+    /// Resolves the target of an invocation in `module` to the procedure it refers to.
+    ///
+    /// Imports are resolved relative to `module` and re-exported aliases are followed to the
+    /// procedure they ultimately refer to.
+    pub fn resolve(&self, module: &ParsedModule, target: &InvocationTarget) -> ResolvedTarget {
+        let mut visited = BTreeSet::new();
+
+        match target {
+            InvocationTarget::MastRoot(digest) => ResolvedTarget::MastRoot(digest.into_inner()),
+            InvocationTarget::ProcedureName(name) => {
+                self.resolve_procedure(module.path(), name, &mut visited)
+            },
+            InvocationTarget::ProcedurePath { name, module: import_name } => {
+                match module.resolve_import(import_name.as_str()) {
+                    Some(path) => self.resolve_procedure(path, name, &mut visited),
+                    None => ResolvedTarget::Unresolved,
+                }
+            },
+            InvocationTarget::AbsoluteProcedurePath { name, path } => {
+                self.resolve_procedure(path, name, &mut visited)
+            },
+        }
+    }
+
+    fn resolve_procedure(
+        &self,
+        module_path: &LibraryPath,
+        name: &ProcedureName,
+        visited: &mut BTreeSet<QualifiedProcedureName>,
+    ) -> ResolvedTarget {
+        let qualified_name = QualifiedProcedureName::new(module_path.clone(), name.clone());
+
+        let Some(module) = self.get(module_path) else {
+            return ResolvedTarget::External(qualified_name);
+        };
+
+        // Guard against aliases that (transitively) re-export themselves.
+        if !visited.insert(qualified_name.clone()) {
+            return ResolvedTarget::Unresolved;
+        }
+
+        match module.procedure(name) {
+            Some(Export::Procedure(_)) => ResolvedTarget::Procedure(qualified_name),
+            Some(Export::Alias(alias)) => match alias.target() {
+                AliasTarget::MastRoot(digest) => ResolvedTarget::MastRoot(digest.into_inner()),
+                AliasTarget::ProcedurePath(target) => {
+                    // The module of a relative alias target is the name of an import.
+                    match module.resolve_import(&target.module.path()) {
+                        Some(path) => self.resolve_procedure(path, &target.name, visited),
+                        None => ResolvedTarget::Unresolved,
+                    }
+                },
+                AliasTarget::AbsoluteProcedurePath(target) => {
+                    self.resolve_procedure(&target.module, &target.name, visited)
+                },
+            },
+            None => ResolvedTarget::Unresolved,
+        }
+    }
+}
+
+impl ParsedModule {
+    /// Returns the targets of all `exec`, `call`, `syscall` and `procref` instructions in the
+    /// procedure bodies of the module.
+    pub fn invocation_targets(&self) -> Vec<InvocationTarget> {
+        self.procedures()
+            .filter_map(|export| match export {
+                Export::Procedure(procedure) => Some(procedure),
+                Export::Alias(_) => None,
+            })
+            .flat_map(invocation_targets)
+            .collect()
+    }
+}
+
+/// Returns the targets of all `exec`, `call`, `syscall` and `procref` instructions in the body of
+/// the procedure.
+pub fn invocation_targets(procedure: &Procedure) -> Vec<InvocationTarget> {
+    let mut collector = InvocationCollector::default();
+    let _ = visit::visit_procedure(&mut collector, procedure);
+    collector.targets
+}
+
+/// The procedure an invocation target refers to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolvedTarget {
+    /// A procedure defined in a module of the graph.
+    Procedure(QualifiedProcedureName),
+    /// A procedure in a module that is not part of the graph, e.g. of the standard library.
+    External(QualifiedProcedureName),
+    /// A procedure referenced by its MAST root, whose definition is unknown.
+    MastRoot(RpoDigest),
+    /// The import or procedure the target refers to does not exist.
+    Unresolved,
+}
+
+#[derive(Default)]
+struct InvocationCollector {
+    targets: Vec<InvocationTarget>,
+}
+
+impl Visit for InvocationCollector {
+    fn visit_invoke_target(&mut self, target: &InvocationTarget) -> ControlFlow<()> {
+        self.targets.push(target.clone());
+        ControlFlow::Continue(())
+    }
+}
@@ -0,0 +1,256 @@
+use std::sync::Arc;
+
+use miden_assembly::{
+    LibraryNamespace, LibraryPath, ModuleParser, Report, SourceFile, SourceSpan, Spanned,
+    ast::{Constant, Export, Ident, Import, Module, ModuleKind, ProcedureName},
+};
+use miette::Diagnostic;
+
+use crate::{
+    LintErrorKind,
+    scanner::{self, ModuleConstants},
+};
+
+/// The name under which modules that cannot be parsed are reported.
+pub const PARSE_ERROR: &str = "parse_error";
+
+/// Parses the source files of a library into modules.
+///
+/// Each module is parsed into its AST with [`ModuleParser`], which runs semantic analysis. Semantic
+/// analysis folds constants into the instructions that use them and drops the constant
+/// definitions from the AST, so the constants are scanned from the source instead and the
+/// references to them are restored in the instructions.
+#[derive(Debug, Clone)]
+pub struct Parser {
+    namespace: LibraryPath,
+}
+
+impl Parser {
+    /// Creates a parser for modules of the library with the given path, e.g. `miden::kernels::tx`.
+    pub fn new(namespace: LibraryPath) -> Self {
+        Self { namespace }
+    }
+
+    /// Parses the source file into a module whose path is derived from the name of the file.
+    ///
+    /// The kind of the module is detected from its content and the namespace: modules with a
+    /// `begin` block are executables, modules in the kernel namespace are kernels and all other
+    /// modules are libraries.
+    ///
+    /// Returns a [`LintErrorKind::ParseError`] if the module has a syntax error or fails semantic
+    /// analysis, e.g. because it defines conflicting procedures or invokes an undefined one.
+    pub fn parse(&self, source_file: Arc<SourceFile>) -> Result<ParsedModule, LintErrorKind> {
+        let path = module_path(&self.namespace, &source_file.name());
+        let kind = if scanner::has_entrypoint(&source_file) {
+            ModuleKind::Executable
+        } else if *self.namespace.namespace() == LibraryNamespace::Kernel {
+            ModuleKind::Kernel
+        } else {
+            ModuleKind::Library
+        };
+
+        let constants = ModuleConstants::scan(&source_file);
+        let mut module = analyze(kind, path, &source_file, &constants)?;
+        scanner::unfold_constants(&mut module, &source_file);
+
+        Ok(ParsedModule { source_file, module, constants })
+    }
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self::new(LibraryPath::new_from_components(LibraryNamespace::Anon, []))
+    }
+}
+
+/// A parsed module of the linted library.
+#[derive(Debug)]
+pub struct ParsedModule {
+    source_file: Arc<SourceFile>,
+    /// The module AST after semantic analysis, which contains the imports and procedures but no
+    /// constants.
+    module: Box<Module>,
+    constants: ModuleConstants,
+}
+
+impl ParsedModule {
+    /// Returns the fully-qualified path of the module, e.g. `miden::kernels::tx::account`.
+    pub fn path(&self) -> &LibraryPath {
+        self.module.path()
+    }
+
+    pub fn kind(&self) -> ModuleKind {
+        self.module.kind()
+    }
+
+    pub fn source_file(&self) -> &Arc<SourceFile> {
+        &self.source_file
+    }
+
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
+
+    pub fn imports(&self) -> impl Iterator<Item = &Import> {
+        self.module.imports()
+    }
+
+    /// Returns the constants defined in the module in the order they appear in the source.
+    pub fn constants(&self) -> impl Iterator<Item = &Constant> {
+        self.constants.constants.iter()
+    }
+
+    /// Returns the names of all constants that are referenced by the immediates of instructions or
+    /// by the values of other constants, once per reference.
+    pub fn constant_references(&self) -> &[Ident] {
+        &self.constants.references
+    }
+
+    /// Returns the names of all constants that are used as the error message of an assertion,
+    /// e.g. `ERR_X` in `assert.err=ERR_X`, once per reference.
+    pub fn error_message_references(&self) -> &[Ident] {
+        &self.constants.error_messages
+    }
+
+    /// Returns all procedures and re-exported aliases defined in the module, including the
+    /// entrypoint of an executable, which is named `main`.
+    pub fn procedures(&self) -> impl Iterator<Item = &Export> {
+        self.module.procedures()
+    }
+
+    /// Returns the procedure or alias with the given name, if the module defines one.
+    pub fn procedure(&self, name: &ProcedureName) -> Option<&Export> {
+        self.procedures().find(|export| export.name() == name)
+    }
+
+    /// Returns the path of the module that is imported under `name`, if any.
+    pub fn resolve_import(&self, name: &str) -> Option<&LibraryPath> {
+        self.imports()
+            .find(|import| import.name.as_str() == name)
+            .map(|import| &import.path)
+    }
+}
+
+/// Parses the source file into a module AST with [`ModuleParser`], which runs semantic analysis.
+///
+/// Constants used with the wrong type are reported before semantic analysis runs, since it panics
+/// on them.
+fn analyze(
+    kind: ModuleKind,
+    path: LibraryPath,
+    source_file: &Arc<SourceFile>,
+    constants: &ModuleConstants,
+) -> Result<Box<Module>, LintErrorKind> {
+    if let Some((reference, expected)) = constants.find_type_mismatch() {
+        return Err(LintErrorKind::ParseError {
+            span: reference.span(),
+            message: format!("constant `{reference}` is used with the wrong type"),
+            label: format!("expected {expected}"),
+            source_file: Arc::clone(source_file),
+        });
+    }
+
+    ModuleParser::new(kind)
+        .parse(path, Arc::clone(source_file))
+        .map_err(|report| parse_error(source_file, report))
+}
+
+/// Converts the report of a parse error into a finding that points at the first label of the
+/// report, or at the start of the file if it has none.
+///
+/// Semantic analysis reports all errors of a module as related errors of a single report, in which
+/// case the first related error that is not a warning is converted.
+fn parse_error(source_file: &Arc<SourceFile>, report: Report) -> LintErrorKind {
+    let related = report.related().and_then(|mut related| {
+        related.find(|diagnostic| {
+            diagnostic.severity().is_none_or(|severity| severity == miette::Severity::Error)
+        })
+    });
+    let diagnostic: &dyn Diagnostic = related.unwrap_or(report.as_ref());
+
+    let label = diagnostic.labels().and_then(|mut labels| labels.next());
+    let range = label
+        .as_ref()
+        .map(|label| label.offset() as u32..(label.offset() + label.len()) as u32)
+        .unwrap_or(0..0);
+
+    LintErrorKind::ParseError {
+        span: SourceSpan::new(source_file.id(), range),
+        message: diagnostic.to_string(),
+        label: label.and_then(|label| label.label().map(String::from)).unwrap_or_default(),
+        source_file: Arc::clone(source_file),
+    }
+}
+
+/// Derives the path of the module in the `namespace` library from the path of its file relative
+/// to the library root.
+///
+/// For example, `account.masm` becomes `namespace::account` and `note/mod.masm` becomes
+/// `namespace::note`.
+fn module_path(namespace: &LibraryPath, relative_file_path: &str) -> LibraryPath {
+    let without_extension = std::path::Path::new(relative_file_path).with_extension("");
+    let mut components: Vec<_> = without_extension
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+
+    if components.last().is_some_and(|last| last == Module::ROOT) {
+        components.pop();
+    }
+
+    components
+        .iter()
+        .fold(namespace.clone(), |path, component| path.append_unchecked(component))
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_assembly::{DefaultSourceManager, SourceManager};
+
+    use super::*;
+
+    fn parse(source: &str) -> Result<ParsedModule, LintErrorKind> {
+        let source_file = DefaultSourceManager::default().load("test.masm", source.to_owned());
+        Parser::default().parse(source_file)
+    }
+
+    #[test]
+    fn constants_are_scanned_from_the_source() {
+        let module = parse("const.A=1\nconst.B=A\n\nproc.foo\n    push.B drop\nend\n").unwrap();
+
+        let names: Vec<_> = module.constants().map(|constant| constant.name.as_str()).collect();
+        assert_eq!(names, ["A", "B"]);
+        let references = module.constant_references();
+        assert_eq!(references.iter().map(Ident::as_str).collect::<Vec<_>>(), ["A", "B"]);
+    }
+
+    #[test]
+    fn modules_in_the_kernel_namespace_are_kernels() {
+        let source_file = DefaultSourceManager::default()
+            .load("account.masm", "export.get_id\n    push.1\nend\n".to_owned());
+        let kernel = LibraryPath::new_from_components(LibraryNamespace::Kernel, []);
+
+        let module = Parser::new(kernel).parse(source_file).unwrap();
+
+        assert_eq!(module.kind(), ModuleKind::Kernel);
+        let Some(Export::Procedure(procedure)) = module.procedures().next() else {
+            panic!("module should export a procedure");
+        };
+        assert!(procedure.visibility().is_syscall());
+    }
+
+    #[test]
+    fn constants_of_the_wrong_type_are_parse_errors() {
+        for source in [
+            "const.A=\"a\"\nconst.B=A\n",
+            "const.A=\"a\"\n\nproc.foo\n    push.A drop\nend\n",
+            "const.A=1\n\nproc.foo\n    assert.err=A\nend\n",
+        ] {
+            let Err(LintErrorKind::ParseError { span, source_file, .. }) = parse(source) else {
+                panic!("source should be a parse error: {source}");
+            };
+            let reference = source_file.source_slice(span.into_slice_index());
+            assert_eq!(reference, Some("A"), "{source}");
+        }
+    }
+}
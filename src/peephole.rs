@@ -0,0 +1,380 @@
+//! Matching of instruction sequences against declarative patterns, such as `push.$imm lt`.
+
+use std::collections::BTreeMap;
+
+use miden_assembly::{
+    SourceFile, SourceSpan,
+    ast::{Block, Op},
+};
+
+/// A pattern of consecutive instructions with a replacement, e.g. `push.$imm lt` with the
+/// replacement `lt.$imm`.
+///
+/// The instructions of a pattern are separated by whitespace and each one is matched against the
+/// canonical textual form of an instruction, e.g. `push.2`, `u32wrapping_add` or `swap.1`, which is
+/// also the form of `swap`. A `$name` placeholder captures a non-empty part of the instruction
+/// without a `.`, such as a single immediate value, which can be used in the replacement. If the
+/// same placeholder appears more than once, all occurrences must capture the same text, so
+/// `movup.$n movdn.$n` only matches if both instructions have the same immediate.
+///
+/// A placeholder written as `$name:u32` only captures a constant name or a literal that fits in a
+/// `u32`, for immediates of instructions such as `u32div` that do not accept larger values.
+///
+/// Instructions that are written in an immediate form that the parser splits into multiple
+/// instructions, e.g. `lt.2` which is parsed as `push.2 lt`, are matched in their written form.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    instructions: Vec<Template>,
+    replacement: Template,
+}
+
+impl Pattern {
+    /// Creates a pattern from its textual form and the replacement of the matched instructions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern is empty or if the replacement uses a placeholder that does not appear
+    /// in the pattern, since patterns are defined statically by lints.
+    pub fn new(pattern: &str, replacement: &str) -> Self {
+        let instructions: Vec<_> = pattern.split_whitespace().map(Template::parse).collect();
+        assert!(!instructions.is_empty(), "pattern `{pattern}` should not be empty");
+
+        let replacement = Template::parse(replacement);
+        for name in replacement.placeholders() {
+            assert!(
+                instructions.iter().any(|template| template.placeholders().any(|n| n == name)),
+                "replacement of pattern `{pattern}` uses unknown placeholder `${name}`"
+            );
+        }
+
+        Self { instructions, replacement }
+    }
+
+    /// Returns the number of instructions the pattern matches.
+    fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    /// Returns the replacement with the captured placeholders if the instructions match the
+    /// pattern.
+    fn matches(&self, instructions: &[WrittenInstruction]) -> Option<String> {
+        if instructions.len() != self.instructions.len() {
+            return None;
+        }
+
+        let mut captures = BTreeMap::new();
+        for (template, instruction) in self.instructions.iter().zip(instructions) {
+            template.capture(&instruction.text, &mut captures)?;
+        }
+
+        Some(self.replacement.render(&captures))
+    }
+}
+
+/// A match of a [`Pattern`] in a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternMatch {
+    /// The span from the start of the first to the end of the last matched instruction.
+    pub span: SourceSpan,
+    /// The index of the matched pattern in the patterns passed to [`find_matches`].
+    pub pattern_index: usize,
+    /// The replacement of the pattern with the captured placeholders.
+    pub replacement: String,
+}
+
+/// Finds all matches of the patterns in the instructions of the block, without descending into
+/// nested blocks.
+///
+/// Patterns only match consecutive instructions, so control flow such as an `if` between two
+/// instructions prevents a match. The block is scanned from the start and at each position the
+/// first matching pattern is taken, so matches do not overlap.
+pub fn find_matches(
+    patterns: &[Pattern],
+    block: &Block,
+    source_file: &SourceFile,
+) -> Vec<PatternMatch> {
+    let mut matches = Vec::new();
+
+    for sequence in written_sequences(block, source_file) {
+        let mut position = 0;
+        while position < sequence.len() {
+            let pattern_match = patterns.iter().enumerate().find_map(|(pattern_index, pattern)| {
+                let instructions = sequence.get(position..position + pattern.len())?;
+                let replacement = pattern.matches(instructions)?;
+                let span = SourceSpan::new(
+                    instructions[0].span.source_id(),
+                    instructions[0].span.start()..instructions[instructions.len() - 1].span.end(),
+                );
+                Some(PatternMatch { span, pattern_index, replacement })
+            });
+
+            match pattern_match {
+                Some(pattern_match) => {
+                    position += patterns[pattern_match.pattern_index].len();
+                    matches.push(pattern_match);
+                },
+                None => position += 1,
+            }
+        }
+    }
+
+    matches
+}
+
+/// An instruction as it is written in the source.
+struct WrittenInstruction {
+    span: SourceSpan,
+    text: String,
+}
+
+/// Returns the sequences of consecutive instructions in the block, split at control flow.
+fn written_sequences(block: &Block, source_file: &SourceFile) -> Vec<Vec<WrittenInstruction>> {
+    let mut sequences: Vec<Vec<WrittenInstruction>> = vec![Vec::new()];
+
+    for op in block.iter() {
+        let Op::Inst(instruction) = op else {
+            sequences.push(Vec::new());
+            continue;
+        };
+
+        let span = instruction.span();
+        let sequence = sequences.last_mut().expect("there should be at least one sequence");
+        match sequence.last_mut() {
+            // The parser splits some instructions, e.g. `lt.2` into `push.2 lt`, which then share
+            // the span of the written instruction.
+            Some(previous) if previous.span == span => {
+                if let Some(text) = source_file.source_slice(span.into_slice_index()) {
+                    previous.text = text.to_owned();
+                }
+            },
+            _ => sequence.push(WrittenInstruction { span, text: instruction.to_string() }),
+        }
+    }
+
+    sequences
+}
+
+/// A text with `$name` placeholders.
+#[derive(Debug, Clone)]
+struct Template {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Literal(String),
+    Placeholder(String, Capture),
+}
+
+/// The kind of text a placeholder captures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Capture {
+    Any,
+    /// A constant name or a literal that fits in a `u32`.
+    U32,
+}
+
+impl Capture {
+    /// The suffix of a placeholder that only captures `u32` immediates.
+    const U32_SUFFIX: &'static str = ":u32";
+
+    fn accepts(self, captured: &str) -> bool {
+        match self {
+            Capture::Any => true,
+            Capture::U32 => {
+                let is_constant =
+                    captured.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_');
+                let value = match captured.strip_prefix("0x") {
+                    Some(hex) => u32::from_str_radix(hex, 16),
+                    None => captured.parse(),
+                };
+                is_constant || value.is_ok()
+            },
+        }
+    }
+}
+
+impl Template {
+    fn parse(text: &str) -> Self {
+        let mut segments = Vec::new();
+        let mut rest = text;
+
+        while let Some(start) = rest.find('$') {
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_owned()));
+            }
+
+            let name_len = rest[start + 1..]
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(rest.len() - start - 1);
+            assert!(name_len > 0, "placeholder in `{text}` should have a name");
+            assert!(
+                !matches!(segments.last(), Some(Segment::Placeholder(..))),
+                "placeholders in `{text}` should be separated by text"
+            );
+
+            let name = rest[start + 1..start + 1 + name_len].to_owned();
+            rest = &rest[start + 1 + name_len..];
+            let capture = match rest.strip_prefix(Capture::U32_SUFFIX) {
+                Some(after_suffix) => {
+                    rest = after_suffix;
+                    Capture::U32
+                },
+                None => Capture::Any,
+            };
+            segments.push(Segment::Placeholder(name, capture));
+        }
+
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_owned()));
+        }
+
+        Self { segments }
+    }
+
+    fn placeholders(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Placeholder(name, _) => Some(name.as_str()),
+            Segment::Literal(_) => None,
+        })
+    }
+
+    /// Matches the text against the template and adds the captured placeholders to `captures`.
+    ///
+    /// A placeholder captures the text up to the first occurrence of the literal that follows it,
+    /// or the rest of the text if it is the last segment. The capture must not contain a `.`, so
+    /// `push.$imm` does not match a push of multiple values.
+    fn capture<'text>(
+        &self,
+        mut text: &'text str,
+        captures: &mut BTreeMap<String, &'text str>,
+    ) -> Option<()> {
+        let mut segments = self.segments.iter().peekable();
+
+        while let Some(segment) = segments.next() {
+            match segment {
+                Segment::Literal(literal) => text = text.strip_prefix(literal.as_str())?,
+                Segment::Placeholder(name, capture) => {
+                    let len = match segments.peek() {
+                        Some(Segment::Literal(literal)) => text.find(literal.as_str())?,
+                        _ => text.len(),
+                    };
+                    let (captured, rest) = text.split_at(len);
+                    if captured.is_empty() || captured.contains('.') || !capture.accepts(captured) {
+                        return None;
+                    }
+
+                    if *captures.entry(name.clone()).or_insert(captured) != captured {
+                        return None;
+                    }
+                    text = rest;
+                },
+            }
+        }
+
+        text.is_empty().then_some(())
+    }
+
+    fn render(&self, captures: &BTreeMap<String, &str>) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(literal) => literal.as_str(),
+                Segment::Placeholder(name, _) => captures.get(name).copied().unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use miden_assembly::{DefaultSourceManager, SourceManager, ast::Export};
+
+    use super::*;
+    use crate::Parser;
+
+    /// Parses the body of a procedure and returns the source text and the replacement of each
+    /// match of the patterns in its top-level block.
+    fn find(patterns: &[Pattern], body: &str) -> Vec<(String, String)> {
+        let source = format!("proc.foo\n{body}\nend\n");
+        let source_manager = DefaultSourceManager::default();
+        let source_file: Arc<SourceFile> = source_manager.load("test.masm", source.clone());
+        let module =
+            Parser::default().parse(Arc::clone(&source_file)).expect("source should parse");
+
+        let Some(Export::Procedure(procedure)) = module.procedures().next() else {
+            panic!("source should start with a procedure");
+        };
+
+        find_matches(patterns, procedure.body(), &source_file)
+            .into_iter()
+            .map(|pattern_match| {
+                (
+                    source[pattern_match.span.into_slice_index()].to_owned(),
+                    pattern_match.replacement,
+                )
+            })
+            .collect()
+    }
+
+    fn matched(text: &str, replacement: &str) -> (String, String) {
+        (text.to_owned(), replacement.to_owned())
+    }
+
+    #[test]
+    fn placeholders_capture_immediates() {
+        let patterns = [Pattern::new("push.$imm lt", "lt.$imm")];
+
+        assert_eq!(find(&patterns, "push.2 lt"), vec![matched("push.2 lt", "lt.2")]);
+        // `lt.2` is parsed as `push.2 lt`, but is matched in its written form.
+        assert_eq!(find(&patterns, "lt.2"), vec![]);
+        // A push of multiple values is parsed as one push per value, of which all but the first
+        // only span the value.
+        assert_eq!(find(&patterns, "push.1.2 lt"), vec![matched("2 lt", "lt.2")]);
+    }
+
+    #[test]
+    fn u32_placeholders_only_capture_u32_immediates() {
+        let patterns = [Pattern::new("push.$imm:u32 u32div", "u32div.$imm")];
+
+        assert_eq!(
+            find(&patterns, "push.4294967295 u32div"),
+            vec![matched("push.4294967295 u32div", "u32div.4294967295")]
+        );
+        assert_eq!(find(&patterns, "push.4294967296 u32div"), vec![]);
+    }
+
+    #[test]
+    fn repeated_placeholders_must_capture_equal_text() {
+        let patterns = [Pattern::new("movup.$n movdn.$n", "")];
+
+        assert_eq!(find(&patterns, "movup.3 movdn.3"), vec![matched("movup.3 movdn.3", "")]);
+        assert_eq!(find(&patterns, "movup.3 movdn.2"), vec![]);
+    }
+
+    #[test]
+    fn instructions_are_matched_in_canonical_form() {
+        let patterns = [Pattern::new("swap.1 swap.1", "")];
+
+        assert_eq!(find(&patterns, "swap swap.1"), vec![matched("swap swap.1", "")]);
+    }
+
+    #[test]
+    fn control_flow_separates_sequences() {
+        let patterns = [Pattern::new("swap.1 swap.1", "")];
+
+        assert_eq!(find(&patterns, "swap if.true add end swap"), vec![]);
+    }
+
+    #[test]
+    fn matches_do_not_overlap_and_the_first_pattern_wins() {
+        let patterns = [Pattern::new("swap.1 swap.1", "first"), Pattern::new("swap.1", "second")];
+
+        assert_eq!(
+            find(&patterns, "swap swap swap"),
+            vec![matched("swap swap", "first"), matched("swap", "second")]
+        );
+    }
+}
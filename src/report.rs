@@ -0,0 +1,46 @@
+use miden_assembly::SourceSpan;
+use miette::Diagnostic;
+use serde::Serialize;
+
+use crate::{LintError, Severity};
+
+/// A serializable representation of a single lint finding, for machine-readable output formats.
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub lint: &'static str,
+    pub severity: Severity,
+    pub file: String,
+    pub start: Position,
+    /// The position right after the last character of the finding.
+    pub end: Position,
+    pub message: String,
+    pub help: Option<String>,
+    pub replacement: Option<String>,
+}
+
+impl From<&LintError> for Finding {
+    fn from(error: &LintError) -> Self {
+        let source_file = error.source_file();
+        let span = error.span();
+        let start = source_file.location(span);
+        let end = source_file.location(SourceSpan::at(span.source_id(), span.end()));
+
+        Self {
+            lint: error.lint_name(),
+            severity: error.severity(),
+            file: source_file.name().to_string(),
+            start: Position { line: start.line, column: start.column },
+            end: Position { line: end.line, column: end.column },
+            message: error.to_string(),
+            help: error.help().map(|help| help.to_string()),
+            replacement: error.fix().map(|fix| fix.replacement().to_owned()),
+        }
+    }
+}
+
+/// A one-indexed line and column in a source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Position {
+    pub line: u32,
+    pub column: u32,
+}
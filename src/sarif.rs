@@ -0,0 +1,213 @@
+//! A minimal model of the [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! format, covering what is needed to report lint findings to code-scanning tools.
+
+use serde::Serialize;
+
+use crate::{LintError, LintInfo, Severity, report::Finding};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+impl SarifLog {
+    /// Creates a log with a single run whose rules are the given lints and whose results are the
+    /// given errors.
+    pub fn new(rules: &[LintInfo], errors: &[LintError]) -> Self {
+        let results = errors
+            .iter()
+            .map(|error| {
+                let rule_index = rules.iter().position(|rule| rule.name == error.lint_name());
+                SarifResult::new(Finding::from(error), rule_index)
+            })
+            .collect();
+
+        let driver = Driver {
+            name: env!("CARGO_PKG_NAME"),
+            version: env!("CARGO_PKG_VERSION"),
+            information_uri: env!("CARGO_PKG_REPOSITORY"),
+            rules: rules.iter().map(Rule::from).collect(),
+        };
+
+        Self {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![Run { tool: Tool { driver }, results }],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Run {
+    tool: Tool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: &'static str,
+    name: &'static str,
+    short_description: Message,
+    help: Message,
+    default_configuration: RuleConfiguration,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct RuleConfiguration {
+    level: &'static str,
+}
+
+impl From<&LintInfo> for Rule {
+    fn from(info: &LintInfo) -> Self {
+        Self {
+            id: info.name,
+            name: info.name,
+            short_description: Message::new(info.description),
+            help: Message::new(info.help),
+            default_configuration: RuleConfiguration { level: level(info.default_severity) },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<SarifFix>,
+}
+
+impl SarifResult {
+    fn new(finding: Finding, rule_index: Option<usize>) -> Self {
+        let message = match &finding.help {
+            Some(help) => format!("{}\nhelp: {help}", finding.message),
+            None => finding.message.clone(),
+        };
+
+        let artifact_location = ArtifactLocation { uri: finding.file.clone() };
+        let region = Region {
+            start_line: finding.start.line,
+            start_column: finding.start.column,
+            end_line: finding.end.line,
+            end_column: finding.end.column,
+        };
+
+        let fixes = finding
+            .replacement
+            .map(|replacement| SarifFix {
+                artifact_changes: vec![ArtifactChange {
+                    artifact_location: artifact_location.clone(),
+                    replacements: vec![Replacement {
+                        deleted_region: region,
+                        inserted_content: Message::new(replacement),
+                    }],
+                }],
+            })
+            .into_iter()
+            .collect();
+
+        Self {
+            rule_id: finding.lint,
+            rule_index,
+            level: level(finding.severity),
+            message: Message::new(message),
+            locations: vec![Location {
+                physical_location: PhysicalLocation { artifact_location, region },
+            }],
+            fixes,
+        }
+    }
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Message {
+    text: String,
+}
+
+impl Message {
+    fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into() }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+/// A region in a source file with one-indexed lines and columns, where the end column is
+/// exclusive.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: u32,
+    start_column: u32,
+    end_line: u32,
+    end_column: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifFix {
+    artifact_changes: Vec<ArtifactChange>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactChange {
+    artifact_location: ArtifactLocation,
+    replacements: Vec<Replacement>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Replacement {
+    deleted_region: Region,
+    inserted_content: Message,
+}
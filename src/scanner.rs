@@ -0,0 +1,376 @@
+//! A scanner for the parts of a module's source that semantic analysis removes from its AST.
+//!
+//! Semantic analysis folds constants into the immediates of the instructions that use them and
+//! drops their definitions, so the constants are recovered from the source text. This works
+//! because constant names are the only identifiers of Miden Assembly that start with an
+//! upper-case letter, so every such identifier outside of comments and strings is either the name
+//! of a constant definition or a reference to a constant.
+
+use std::{
+    ops::{ControlFlow, Range},
+    sync::Arc,
+};
+
+use miden_assembly::{
+    SourceFile, SourceSpan, Span, Spanned,
+    ast::{
+        Constant, ConstantExpr, ConstantOp, Ident, Immediate, Module,
+        visit::{self, VisitMut},
+    },
+};
+use miden_core::{Felt, StarkField};
+
+/// The constants that are defined and referenced in a module.
+#[derive(Debug, Default)]
+pub(crate) struct ModuleConstants {
+    /// The constants defined in the module in the order they appear in the source.
+    pub constants: Vec<Constant>,
+    /// The names of all constants that are referenced by the immediates of instructions or by the
+    /// values of other constants, once per reference.
+    pub references: Vec<Ident>,
+    /// The references that are error messages of assertions, which are part of `references` too.
+    pub error_messages: Vec<Ident>,
+}
+
+impl ModuleConstants {
+    /// Scans the source file for constant definitions and references.
+    ///
+    /// Definitions whose value cannot be parsed are skipped, since parsing the module reports them
+    /// as syntax errors.
+    pub fn scan(source_file: &SourceFile) -> Self {
+        let tokens = tokenize(source_file.as_str());
+        let mut scanner = ConstantScanner { source_file, tokens: &tokens, pos: 0 };
+        let mut constants = Self::default();
+
+        while scanner.pos < tokens.len() {
+            if let Some(constant) = scanner.constant() {
+                constants.collect_references(&constant.value);
+                constants.constants.push(constant);
+                continue;
+            }
+
+            let token = &tokens[scanner.pos];
+            if token.kind == TokenKind::Constant {
+                let name = scanner.ident(token);
+                // Error messages are written as `assert.err=ERR_X`.
+                let is_error_message = scanner.pos >= 2
+                    && tokens[scanner.pos - 2].text == "err"
+                    && tokens[scanner.pos - 1].text == "=";
+                if is_error_message {
+                    constants.error_messages.push(name.clone());
+                }
+                constants.references.push(name);
+            }
+            scanner.pos += 1;
+        }
+
+        constants
+    }
+
+    /// Returns the first reference that uses a constant of the wrong type, together with the
+    /// expected type: string constants can only be used as error messages, and all other
+    /// constants only as felts.
+    ///
+    /// Semantic analysis in `miden-assembly` 0.15 panics on such references instead of reporting
+    /// them, so they have to be found before the module is parsed.
+    pub fn find_type_mismatch(&self) -> Option<(&Ident, &'static str)> {
+        self.references.iter().find_map(|reference| {
+            let constant = self
+                .constants
+                .iter()
+                .find(|constant| constant.name.as_str() == reference.as_str())?;
+            let is_string = matches!(constant.value, ConstantExpr::String(_));
+            let is_error_message =
+                self.error_messages.iter().any(|message| message.span() == reference.span());
+            let expected = if is_error_message { "a string" } else { "a felt" };
+            (is_string != is_error_message).then_some((reference, expected))
+        })
+    }
+
+    fn collect_references(&mut self, expr: &ConstantExpr) {
+        match expr {
+            ConstantExpr::Var(name) => self.references.push(name.clone()),
+            ConstantExpr::BinaryOp { lhs, rhs, .. } => {
+                self.collect_references(lhs);
+                self.collect_references(rhs);
+            },
+            ConstantExpr::Literal(_) | ConstantExpr::String(_) => {},
+        }
+    }
+}
+
+/// Returns `true` if the source file has a `begin` block, i.e. is an executable.
+pub(crate) fn has_entrypoint(source_file: &SourceFile) -> bool {
+    tokenize(source_file.as_str())
+        .iter()
+        .any(|token| token.kind == TokenKind::Word && token.text == "begin")
+}
+
+/// Returns `true` if the code at `span` is the name of a constant, e.g. the span of an immediate
+/// that semantic analysis folded into its value.
+pub(crate) fn is_constant_name(source_file: &SourceFile, span: SourceSpan) -> bool {
+    source_file
+        .source_slice(span.into_slice_index())
+        .is_some_and(|source| source.starts_with(|c: char| c.is_ascii_uppercase()))
+}
+
+/// Restores the constants that semantic analysis folded into the immediates of the module's
+/// instructions, so that lints see `push.ADDR` as written instead of `push.8`.
+pub(crate) fn unfold_constants(module: &mut Module, source_file: &SourceFile) {
+    let _ = visit::visit_mut_module(&mut ConstantUnfolder { source_file }, module);
+}
+
+struct ConstantUnfolder<'a> {
+    source_file: &'a SourceFile,
+}
+
+impl ConstantUnfolder<'_> {
+    fn unfold<T>(&self, imm: &mut Immediate<T>) -> ControlFlow<()> {
+        if let Immediate::Value(value) = imm
+            && is_constant_name(self.source_file, value.span())
+            && let Some(name) = self.source_file.source_slice(value.span().into_slice_index())
+        {
+            *imm = Immediate::Constant(Ident::from_raw_parts(Span::new(value.span(), name.into())));
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl VisitMut for ConstantUnfolder<'_> {
+    fn visit_mut_immediate_u8(&mut self, imm: &mut Immediate<u8>) -> ControlFlow<()> {
+        self.unfold(imm)
+    }
+
+    fn visit_mut_immediate_u16(&mut self, imm: &mut Immediate<u16>) -> ControlFlow<()> {
+        self.unfold(imm)
+    }
+
+    fn visit_mut_immediate_u32(&mut self, imm: &mut Immediate<u32>) -> ControlFlow<()> {
+        self.unfold(imm)
+    }
+
+    fn visit_mut_immediate_felt(&mut self, imm: &mut Immediate<Felt>) -> ControlFlow<()> {
+        self.unfold(imm)
+    }
+
+    fn visit_mut_immediate_error_message(
+        &mut self,
+        imm: &mut Immediate<Arc<str>>,
+    ) -> ControlFlow<()> {
+        self.unfold(imm)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    /// A keyword, an instruction or the name of a procedure or module.
+    Word,
+    /// The name of a constant.
+    Constant,
+    Number,
+    /// A quoted string, including the quotes.
+    String,
+    /// Any other character, or `//`.
+    Punct,
+}
+
+#[derive(Debug)]
+struct Token<'a> {
+    kind: TokenKind,
+    text: &'a str,
+    range: Range<usize>,
+}
+
+/// Splits the source into tokens, skipping whitespace and comments.
+fn tokenize(source: &str) -> Vec<Token<'_>> {
+    let bytes = source.as_bytes();
+    let scan_while = |start: usize, accept: fn(&u8) -> bool| {
+        bytes[start..]
+            .iter()
+            .position(|c| !accept(c))
+            .map_or(bytes.len(), |len| start + len)
+    };
+
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while let Some(&c) = bytes.get(pos) {
+        let start = pos;
+        let kind = match c {
+            b'#' => {
+                pos = source[pos..].find('\n').map_or(bytes.len(), |len| pos + len);
+                continue;
+            },
+            c if c.is_ascii_whitespace() => {
+                pos += 1;
+                continue;
+            },
+            b'"' => {
+                pos = source[pos + 1..].find('"').map_or(bytes.len(), |len| pos + len + 2);
+                TokenKind::String
+            },
+            b'0'..=b'9' => {
+                pos = scan_while(pos, |c| c.is_ascii_alphanumeric() || *c == b'_');
+                TokenKind::Number
+            },
+            b'A'..=b'Z' => {
+                pos =
+                    scan_while(pos, |c| c.is_ascii_uppercase() || c.is_ascii_digit() || *c == b'_');
+                TokenKind::Constant
+            },
+            b'a'..=b'z' | b'_' => {
+                pos = scan_while(pos, |c| c.is_ascii_alphanumeric() || *c == b'_');
+                TokenKind::Word
+            },
+            b'/' if bytes.get(pos + 1) == Some(&b'/') => {
+                pos += 2;
+                TokenKind::Punct
+            },
+            _ => {
+                pos += source[pos..].chars().next().map_or(1, char::len_utf8);
+                TokenKind::Punct
+            },
+        };
+        tokens.push(Token {
+            kind,
+            text: &source[start..pos],
+            range: start..pos,
+        });
+    }
+
+    tokens
+}
+
+/// Parses constant definitions from the tokens of a module.
+struct ConstantScanner<'a> {
+    source_file: &'a SourceFile,
+    tokens: &'a [Token<'a>],
+    pos: usize,
+}
+
+impl<'a> ConstantScanner<'a> {
+    /// Parses the constant definition at the current position, e.g. `const.A=B*2`, and advances
+    /// past it, or returns `None` and leaves the position unchanged if there is none.
+    fn constant(&mut self) -> Option<Constant> {
+        let start = self.pos;
+        let constant = self.definition();
+        if constant.is_none() {
+            self.pos = start;
+        }
+        constant
+    }
+
+    fn definition(&mut self) -> Option<Constant> {
+        let keyword =
+            self.next_if(|token| token.kind == TokenKind::Word && token.text == "const")?;
+        self.next_if(|token| token.text == ".")?;
+        let name = self.next_if(|token| token.kind == TokenKind::Constant)?;
+        let name = self.ident(name);
+        self.next_if(|token| token.text == "=")?;
+        let value = self.expr()?;
+
+        let end = self.tokens[self.pos - 1].range.end;
+        let span = self.span(keyword.range.start..end);
+        Some(Constant::new(span, name, value))
+    }
+
+    /// Parses a sum or difference of products.
+    fn expr(&mut self) -> Option<ConstantExpr> {
+        let mut lhs = self.product()?;
+        while let Some(op) = self.operator(&[("+", ConstantOp::Add), ("-", ConstantOp::Sub)]) {
+            lhs = self.binary_op(op, lhs, Self::product)?;
+        }
+        Some(lhs)
+    }
+
+    /// Parses a product or quotient of terms.
+    fn product(&mut self) -> Option<ConstantExpr> {
+        let operators =
+            [("*", ConstantOp::Mul), ("/", ConstantOp::Div), ("//", ConstantOp::IntDiv)];
+        let mut lhs = self.term()?;
+        while let Some(op) = self.operator(&operators) {
+            lhs = self.binary_op(op, lhs, Self::term)?;
+        }
+        Some(lhs)
+    }
+
+    fn term(&mut self) -> Option<ConstantExpr> {
+        let token = self.next_if(|_| true)?;
+        match token.kind {
+            TokenKind::Punct if token.text == "(" => {
+                let expr = self.expr()?;
+                self.next_if(|token| token.text == ")")?;
+                Some(expr)
+            },
+            TokenKind::Number => {
+                let felt = parse_felt(token.text)?;
+                Some(ConstantExpr::Literal(Span::new(self.span(token.range.clone()), felt)))
+            },
+            TokenKind::String if token.text.len() >= 2 => {
+                // The span of a string excludes the quotes.
+                let range = token.range.start + 1..token.range.end - 1;
+                let value = Arc::from(&token.text[1..token.text.len() - 1]);
+                Some(ConstantExpr::String(Ident::from_raw_parts(Span::new(
+                    self.span(range),
+                    value,
+                ))))
+            },
+            TokenKind::Constant => Some(ConstantExpr::Var(self.ident(token))),
+            _ => None,
+        }
+    }
+
+    /// Parses the right-hand side of a binary operation with `parse_rhs` and folds the operation
+    /// if both sides are literals, like the parser does.
+    fn binary_op(
+        &mut self,
+        op: ConstantOp,
+        lhs: ConstantExpr,
+        parse_rhs: fn(&mut Self) -> Option<ConstantExpr>,
+    ) -> Option<ConstantExpr> {
+        let rhs = parse_rhs(self)?;
+        let span = self.span(lhs.span().start().to_usize()..rhs.span().end().to_usize());
+        let expr = ConstantExpr::BinaryOp {
+            span,
+            op,
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+        };
+        expr.try_fold().ok()
+    }
+
+    fn operator(&mut self, operators: &[(&str, ConstantOp)]) -> Option<ConstantOp> {
+        let token = self.tokens.get(self.pos).filter(|token| token.kind == TokenKind::Punct)?;
+        let (_, op) = operators.iter().find(|(text, _)| *text == token.text)?;
+        self.pos += 1;
+        Some(*op)
+    }
+
+    fn next_if(&mut self, accept: impl Fn(&Token) -> bool) -> Option<&'a Token<'a>> {
+        let tokens = self.tokens;
+        let token = tokens.get(self.pos).filter(|token| accept(token))?;
+        self.pos += 1;
+        Some(token)
+    }
+
+    fn ident(&self, token: &Token) -> Ident {
+        Ident::from_raw_parts(Span::new(self.span(token.range.clone()), Arc::from(token.text)))
+    }
+
+    fn span(&self, range: Range<usize>) -> SourceSpan {
+        SourceSpan::new(self.source_file.id(), range.start as u32..range.end as u32)
+    }
+}
+
+/// Parses a decimal, hexadecimal or binary field element, or returns `None` if it is not a valid
+/// one.
+fn parse_felt(text: &str) -> Option<Felt> {
+    let value = if let Some(hex) = text.strip_prefix("0x") {
+        u64::from_str_radix(hex, 16).ok()?
+    } else if let Some(binary) = text.strip_prefix("0b") {
+        u64::from_str_radix(binary, 2).ok()?
+    } else {
+        text.parse().ok()?
+    };
+
+    (value < Felt::MODULUS).then(|| Felt::new(value))
+}
@@ -0,0 +1,476 @@
+//! Inference of the effect that instructions, blocks and procedures have on the operand stack.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use miden_assembly::ast::{
+    Block, Export, Immediate, Instruction, InvocationTarget, Module, Op, Procedure, ProcedureName,
+};
+
+/// The number of elements an instruction or block consumes from the top of the operand stack and
+/// the number of elements it produces in their place.
+///
+/// Instructions that only rearrange or inspect elements consume and produce the elements they
+/// access, e.g. `swap.2` has an effect of `3 -> 3` and `dup.1` has an effect of `2 -> 3`. The
+/// number of consumed elements is therefore the depth of the stack the instruction accesses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StackEffect {
+    pub inputs: usize,
+    pub outputs: usize,
+}
+
+impl StackEffect {
+    /// The effect of code that does not access the stack.
+    pub const NONE: Self = Self::new(0, 0);
+
+    pub const fn new(inputs: usize, outputs: usize) -> Self {
+        Self { inputs, outputs }
+    }
+
+    /// Returns by how much the depth of the stack changes.
+    pub fn delta(&self) -> isize {
+        self.outputs as isize - self.inputs as isize
+    }
+
+    /// Returns the effect of executing `self` followed by `next`.
+    pub fn then(self, next: Self) -> Self {
+        if self.outputs >= next.inputs {
+            Self::new(self.inputs, self.outputs - next.inputs + next.outputs)
+        } else {
+            Self::new(self.inputs + next.inputs - self.outputs, next.outputs)
+        }
+    }
+
+    /// Returns the effect of executing either `self` or `other`, which is only known if both
+    /// change the depth of the stack by the same amount.
+    pub fn either(self, other: Self) -> Option<Self> {
+        if self.delta() != other.delta() {
+            return None;
+        }
+
+        let inputs = self.inputs.max(other.inputs);
+        Some(Self::new(inputs, inputs.checked_add_signed(self.delta())?))
+    }
+
+    /// Returns the effect of the instruction, or `None` if it is unknown.
+    ///
+    /// The effect of invocations is always unknown, since the invoked procedure is not known. Use
+    /// [`StackEffectInference::instruction`] to resolve the effect of procedures in the same
+    /// module.
+    pub fn of_instruction(instruction: &Instruction) -> Option<Self> {
+        use Instruction::*;
+
+        let effect = match instruction {
+            Nop | Breakpoint | Debug(_) | Emit(_) | Trace(_) | SysEvent(_) => (0, 0),
+
+            // Assertions
+            Assert | AssertWithError(_) | Assertz | AssertzWithError(_) => (1, 0),
+            AssertEq | AssertEqWithError(_) => (2, 0),
+            AssertEqw | AssertEqwWithError(_) => (8, 0),
+
+            // Field operations
+            Add | Sub | Mul | Div | Exp | ExpBitLength(_) | And | Or | Xor | Eq | Neq | Lt
+            | Lte | Gt | Gte => (2, 1),
+            AddImm(_) | SubImm(_) | MulImm(_) | DivImm(_) | ExpImm(_) | EqImm(_) | NeqImm(_)
+            | Neg | ILog2 | Inv | Incr | Pow2 | Not | IsOdd => (1, 1),
+            Eqw => (8, 9),
+
+            // Extension field operations
+            Ext2Add | Ext2Sub | Ext2Mul | Ext2Div => (4, 2),
+            Ext2Neg | Ext2Inv => (2, 2),
+
+            // u32 operations
+            U32Test => (1, 2),
+            U32TestW => (4, 5),
+            U32Assert | U32AssertWithError(_) | U32Cast => (1, 1),
+            U32Assert2 | U32Assert2WithError(_) => (2, 2),
+            U32AssertW | U32AssertWWithError(_) => (4, 4),
+            U32Split => (1, 2),
+            U32WrappingAdd | U32WrappingSub | U32WrappingMul | U32Div | U32Mod | U32And | U32Or
+            | U32Xor | U32Shr | U32Shl | U32Rotr | U32Rotl | U32Lt | U32Lte | U32Gt | U32Gte
+            | U32Min | U32Max => (2, 1),
+            U32WrappingAddImm(_) | U32WrappingSubImm(_) | U32WrappingMulImm(_) | U32DivImm(_)
+            | U32ModImm(_) | U32ShrImm(_) | U32ShlImm(_) | U32RotrImm(_) | U32RotlImm(_)
+            | U32Not | U32Popcnt | U32Ctz | U32Clz | U32Clo | U32Cto => (1, 1),
+            U32OverflowingAdd | U32OverflowingSub | U32OverflowingMul | U32DivMod => (2, 2),
+            U32OverflowingAddImm(_)
+            | U32OverflowingSubImm(_)
+            | U32OverflowingMulImm(_)
+            | U32DivModImm(_) => (1, 2),
+            U32OverflowingAdd3 | U32OverflowingMadd => (3, 2),
+            U32WrappingAdd3 | U32WrappingMadd => (3, 1),
+
+            // Stack manipulation
+            Drop => (1, 0),
+            DropW => (4, 0),
+            PadW => (0, 4),
+            Dup0 => (1, 2),
+            Dup1 => (2, 3),
+            Dup2 => (3, 4),
+            Dup3 => (4, 5),
+            Dup4 => (5, 6),
+            Dup5 => (6, 7),
+            Dup6 => (7, 8),
+            Dup7 => (8, 9),
+            Dup8 => (9, 10),
+            Dup9 => (10, 11),
+            Dup10 => (11, 12),
+            Dup11 => (12, 13),
+            Dup12 => (13, 14),
+            Dup13 => (14, 15),
+            Dup14 => (15, 16),
+            Dup15 => (16, 17),
+            DupW0 => (4, 8),
+            DupW1 => (8, 12),
+            DupW2 => (12, 16),
+            DupW3 => (16, 20),
+            Swap1 => (2, 2),
+            Swap2 | MovUp2 | MovDn2 => (3, 3),
+            Swap3 | MovUp3 | MovDn3 => (4, 4),
+            Swap4 | MovUp4 | MovDn4 => (5, 5),
+            Swap5 | MovUp5 | MovDn5 => (6, 6),
+            Swap6 | MovUp6 | MovDn6 => (7, 7),
+            Swap7 | MovUp7 | MovDn7 | SwapW1 => (8, 8),
+            Swap8 | MovUp8 | MovDn8 => (9, 9),
+            Swap9 | MovUp9 | MovDn9 => (10, 10),
+            Swap10 | MovUp10 | MovDn10 => (11, 11),
+            Swap11 | MovUp11 | MovDn11 | SwapW2 | MovUpW2 | MovDnW2 => (12, 12),
+            Swap12 | MovUp12 | MovDn12 => (13, 13),
+            Swap13 | MovUp13 | MovDn13 => (14, 14),
+            Swap14 | MovUp14 | MovDn14 => (15, 15),
+            Swap15 | MovUp15 | MovDn15 | SwapW3 | MovUpW3 | MovDnW3 | SwapDw => (16, 16),
+            CSwap => (3, 2),
+            CSwapW => (9, 8),
+            CDrop => (3, 1),
+            CDropW => (9, 4),
+
+            // Input and output operations
+            Push(_) | PushU8(_) | PushU16(_) | PushU32(_) | PushFelt(_) | Locaddr(_) | Sdepth
+            | Clk | MemLoadImm(_) | LocLoad(_) => (0, 1),
+            PushWord(_) | ProcRef(_) => (0, 4),
+            PushU8List(values) => (0, values.len()),
+            PushU16List(values) => (0, values.len()),
+            PushU32List(values) => (0, values.len()),
+            PushFeltList(values) => (0, values.len()),
+            Caller | MemLoadWImm(_) | LocLoadW(_) | MemStoreWImm(_) | LocStoreW(_) | AdvLoadW => {
+                (4, 4)
+            },
+            MemLoad => (1, 1),
+            MemLoadW | MemStoreW => (5, 4),
+            MemStore => (2, 0),
+            MemStoreImm(_) | LocStore(_) => (1, 0),
+            MemStream | AdvPipe => (13, 13),
+            AdvPush(Immediate::Value(count)) => (0, usize::from(count.into_inner())),
+            AdvPush(Immediate::Constant(_)) => return None,
+
+            // Cryptographic operations
+            Hash => (4, 4),
+            HMerge | MTreeMerge => (8, 4),
+            HPerm => (12, 12),
+            MTreeGet => (6, 8),
+            MTreeSet => (10, 8),
+            MTreeVerify | MTreeVerifyWithError(_) => (10, 10),
+            FriExt2Fold4 => (17, 16),
+            HornerBase | HornerExt => (16, 16),
+            ArithmeticCircuitEval => return None,
+
+            // Invocations
+            Exec(_) | Call(_) | SysCall(_) | DynExec | DynCall => return None,
+        };
+
+        Some(Self::new(effect.0, effect.1))
+    }
+}
+
+/// Infers the stack effects of code in a module, resolving the effects of procedures that are
+/// executed with `exec` from the same module.
+///
+/// The effects of all other invocations are unknown: `call`, `syscall` and dynamic invocations,
+/// and `exec`s of procedures in other modules or of procedures whose effect is itself unknown,
+/// e.g. because they are recursive.
+#[derive(Debug, Clone, Default)]
+pub struct StackEffectInference {
+    procedures: BTreeMap<ProcedureName, Option<StackEffect>>,
+}
+
+impl StackEffectInference {
+    /// Infers the stack effects of all procedures defined in a module.
+    pub fn new(module: &Module) -> Self {
+        let bodies = module
+            .procedures()
+            .filter_map(|export| match export {
+                Export::Procedure(procedure) => Some((procedure.name().clone(), procedure)),
+                Export::Alias(_) => None,
+            })
+            .collect();
+
+        let mut resolver = ProcedureResolver {
+            bodies,
+            inference: Self::default(),
+            in_progress: BTreeSet::new(),
+        };
+        let names: Vec<_> = resolver.bodies.keys().cloned().collect();
+        for name in names {
+            resolver.procedure(&name);
+        }
+
+        resolver.inference
+    }
+
+    /// Returns the stack effect of the procedure with the given name in the module, or `None` if
+    /// it is unknown.
+    pub fn procedure(&self, name: &ProcedureName) -> Option<StackEffect> {
+        self.procedures.get(name).copied().flatten()
+    }
+
+    /// Returns the stack effect of the instruction, or `None` if it is unknown.
+    pub fn instruction(&self, instruction: &Instruction) -> Option<StackEffect> {
+        match instruction {
+            Instruction::Exec(InvocationTarget::ProcedureName(name)) => self.procedure(name),
+            _ => StackEffect::of_instruction(instruction),
+        }
+    }
+
+    /// Returns the stack effect of the block, or `None` if it is unknown.
+    pub fn block(&self, block: &Block) -> Option<StackEffect> {
+        block_effect(block, &mut |instruction| self.instruction(instruction))
+    }
+}
+
+/// Returns the effect of the block, where the effect of each instruction is given by
+/// `instruction_effect`.
+fn block_effect(
+    block: &Block,
+    instruction_effect: &mut impl FnMut(&Instruction) -> Option<StackEffect>,
+) -> Option<StackEffect> {
+    let mut effect = StackEffect::NONE;
+
+    for op in block.iter() {
+        let op_effect = match op {
+            Op::If { then_blk, else_blk, .. } => {
+                let then_effect = block_effect(then_blk, instruction_effect)?;
+                let else_effect = block_effect(else_blk, instruction_effect)?;
+                if_effect(then_effect, else_effect)?
+            },
+            Op::While { body, .. } => while_effect(block_effect(body, instruction_effect)?)?,
+            Op::Repeat { count, body, .. } => {
+                repeat_effect(block_effect(body, instruction_effect)?, *count)
+            },
+            Op::Inst(instruction) => instruction_effect(instruction)?,
+        };
+
+        effect = effect.then(op_effect);
+    }
+
+    Some(effect)
+}
+
+/// The effect of popping the condition of an `if` or `while`.
+const POP_CONDITION: StackEffect = StackEffect::new(1, 0);
+
+/// Returns the effect of an `if` with the given effects of its branches.
+fn if_effect(then_effect: StackEffect, else_effect: StackEffect) -> Option<StackEffect> {
+    Some(POP_CONDITION.then(then_effect.either(else_effect)?))
+}
+
+/// Returns the effect of a `while` loop with the given effect of its body.
+///
+/// The effect is only known if each iteration leaves the stack as deep as it was before the
+/// condition was popped, i.e. the body pushes exactly one element more than it consumes.
+fn while_effect(body_effect: StackEffect) -> Option<StackEffect> {
+    let iteration = body_effect.then(POP_CONDITION);
+    if iteration.delta() != 0 {
+        return None;
+    }
+
+    Some(POP_CONDITION.then(iteration.either(StackEffect::NONE)?))
+}
+
+/// Returns the effect of a `repeat` loop with the given effect of its body.
+fn repeat_effect(body_effect: StackEffect, count: u32) -> StackEffect {
+    let count = count as usize;
+    if count == 0 {
+        return StackEffect::NONE;
+    }
+
+    // Each iteration consumes the elements that the previous one produced, so only the growth or
+    // shrinkage of the stack accumulates.
+    let StackEffect { inputs, outputs } = body_effect;
+    if outputs >= inputs {
+        StackEffect::new(inputs, outputs + (count - 1) * (outputs - inputs))
+    } else {
+        StackEffect::new(inputs + (count - 1) * (inputs - outputs), outputs)
+    }
+}
+
+struct ProcedureResolver<'module> {
+    bodies: BTreeMap<ProcedureName, &'module Procedure>,
+    inference: StackEffectInference,
+    /// The procedures whose effect is currently being inferred, to detect recursion.
+    in_progress: BTreeSet<ProcedureName>,
+}
+
+impl ProcedureResolver<'_> {
+    fn procedure(&mut self, name: &ProcedureName) -> Option<StackEffect> {
+        if let Some(effect) = self.inference.procedures.get(name) {
+            return *effect;
+        }
+        if !self.in_progress.insert(name.clone()) {
+            return None;
+        }
+
+        let procedure = self.bodies.get(name).copied();
+        let effect = procedure.and_then(|procedure| {
+            block_effect(procedure.body(), &mut |instruction| match instruction {
+                Instruction::Exec(InvocationTarget::ProcedureName(name)) => self.procedure(name),
+                _ => StackEffect::of_instruction(instruction),
+            })
+        });
+
+        self.in_progress.remove(name);
+        self.inference.procedures.insert(name.clone(), effect);
+        effect
+    }
+}
+
+/// An instruction that only duplicates or reorders elements or words on the stack, with the
+/// positions it accesses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shuffle {
+    /// `dup.n`, which pushes a copy of the element at position `n`.
+    Dup(usize),
+    /// `dupw.n`, which pushes a copy of the word at position `n`.
+    DupW(usize),
+    /// `swap.n`, which swaps the top element with the element at position `n`.
+    Swap(usize),
+    /// `swapw.n`, which swaps the top word with the word at position `n`.
+    SwapW(usize),
+    /// `swapdw`, which swaps the top two words with the next two words.
+    SwapDw,
+    /// `movup.n`, which moves the element at position `n` to the top.
+    MovUp(usize),
+    /// `movdn.n`, which moves the top element to position `n`.
+    MovDn(usize),
+    /// `movupw.n`, which moves the word at position `n` to the top.
+    MovUpW(usize),
+    /// `movdnw.n`, which moves the top word to position `n`.
+    MovDnW(usize),
+}
+
+impl Shuffle {
+    /// Returns the shuffle the instruction performs, if it is a shuffle instruction.
+    pub fn of_instruction(instruction: &Instruction) -> Option<Self> {
+        use Instruction::*;
+
+        let shuffle = match instruction {
+            Dup0 => Self::Dup(0),
+            Dup1 => Self::Dup(1),
+            Dup2 => Self::Dup(2),
+            Dup3 => Self::Dup(3),
+            Dup4 => Self::Dup(4),
+            Dup5 => Self::Dup(5),
+            Dup6 => Self::Dup(6),
+            Dup7 => Self::Dup(7),
+            Dup8 => Self::Dup(8),
+            Dup9 => Self::Dup(9),
+            Dup10 => Self::Dup(10),
+            Dup11 => Self::Dup(11),
+            Dup12 => Self::Dup(12),
+            Dup13 => Self::Dup(13),
+            Dup14 => Self::Dup(14),
+            Dup15 => Self::Dup(15),
+            DupW0 => Self::DupW(0),
+            DupW1 => Self::DupW(1),
+            DupW2 => Self::DupW(2),
+            DupW3 => Self::DupW(3),
+            Swap1 => Self::Swap(1),
+            Swap2 => Self::Swap(2),
+            Swap3 => Self::Swap(3),
+            Swap4 => Self::Swap(4),
+            Swap5 => Self::Swap(5),
+            Swap6 => Self::Swap(6),
+            Swap7 => Self::Swap(7),
+            Swap8 => Self::Swap(8),
+            Swap9 => Self::Swap(9),
+            Swap10 => Self::Swap(10),
+            Swap11 => Self::Swap(11),
+            Swap12 => Self::Swap(12),
+            Swap13 => Self::Swap(13),
+            Swap14 => Self::Swap(14),
+            Swap15 => Self::Swap(15),
+            SwapW1 => Self::SwapW(1),
+            SwapW2 => Self::SwapW(2),
+            SwapW3 => Self::SwapW(3),
+            SwapDw => Self::SwapDw,
+            MovUp2 => Self::MovUp(2),
+            MovUp3 => Self::MovUp(3),
+            MovUp4 => Self::MovUp(4),
+            MovUp5 => Self::MovUp(5),
+            MovUp6 => Self::MovUp(6),
+            MovUp7 => Self::MovUp(7),
+            MovUp8 => Self::MovUp(8),
+            MovUp9 => Self::MovUp(9),
+            MovUp10 => Self::MovUp(10),
+            MovUp11 => Self::MovUp(11),
+            MovUp12 => Self::MovUp(12),
+            MovUp13 => Self::MovUp(13),
+            MovUp14 => Self::MovUp(14),
+            MovUp15 => Self::MovUp(15),
+            MovUpW2 => Self::MovUpW(2),
+            MovUpW3 => Self::MovUpW(3),
+            MovDn2 => Self::MovDn(2),
+            MovDn3 => Self::MovDn(3),
+            MovDn4 => Self::MovDn(4),
+            MovDn5 => Self::MovDn(5),
+            MovDn6 => Self::MovDn(6),
+            MovDn7 => Self::MovDn(7),
+            MovDn8 => Self::MovDn(8),
+            MovDn9 => Self::MovDn(9),
+            MovDn10 => Self::MovDn(10),
+            MovDn11 => Self::MovDn(11),
+            MovDn12 => Self::MovDn(12),
+            MovDn13 => Self::MovDn(13),
+            MovDn14 => Self::MovDn(14),
+            MovDn15 => Self::MovDn(15),
+            MovDnW2 => Self::MovDnW(2),
+            MovDnW3 => Self::MovDnW(3),
+            _ => return None,
+        };
+
+        Some(shuffle)
+    }
+
+    /// Applies the shuffle to the elements of a stack whose top is the first element.
+    ///
+    /// Returns `None` if the stack has fewer elements than the shuffle accesses.
+    pub fn apply<T: Clone>(&self, stack: &mut Vec<T>) -> Option<()> {
+        if stack.len() < self.depth() {
+            return None;
+        }
+
+        match *self {
+            Self::Dup(n) => stack.insert(0, stack[n].clone()),
+            Self::DupW(n) => {
+                let word = stack[4 * n..4 * n + 4].to_vec();
+                stack.splice(0..0, word);
+            },
+            Self::Swap(n) => stack.swap(0, n),
+            Self::SwapW(n) => (0..4).for_each(|i| stack.swap(i, 4 * n + i)),
+            Self::SwapDw => (0..8).for_each(|i| stack.swap(i, 8 + i)),
+            Self::MovUp(n) => stack[..=n].rotate_right(1),
+            Self::MovDn(n) => stack[..=n].rotate_left(1),
+            Self::MovUpW(n) => stack[..4 * n + 4].rotate_right(4),
+            Self::MovDnW(n) => stack[..4 * n + 4].rotate_left(4),
+        }
+
+        Some(())
+    }
+
+    /// Returns the number of elements at the top of the stack the shuffle accesses.
+    pub fn depth(&self) -> usize {
+        match *self {
+            Self::Dup(n) | Self::Swap(n) | Self::MovUp(n) | Self::MovDn(n) => n + 1,
+            Self::DupW(n) | Self::SwapW(n) | Self::MovUpW(n) | Self::MovDnW(n) => 4 * n + 4,
+            Self::SwapDw => 16,
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::{ops::Range, sync::Arc};
+
+use miden_assembly::{
+    SourceFile, SourceSpan, Spanned,
+    ast::{Export, Module},
+};
+
+use crate::LintErrorKind;
+
+const SUPPRESSION_PREFIX: &str = "masmlint:";
+
+/// The suppression comments of a source file, such as `# masmlint: allow(bare_assert)`.
+///
+/// The scope of a suppression depends on what follows the comment:
+/// - If the comment is at the top of the module, i.e. only preceded by other comments, and it is
+///   followed by a blank line, it applies to the whole file.
+/// - If it is followed by a procedure definition, it applies to the whole procedure.
+/// - Otherwise, it applies to the next line of code.
+pub(crate) struct Suppressions {
+    source_file: Arc<SourceFile>,
+    suppressions: Vec<Suppression>,
+}
+
+struct Suppression {
+    lint_name: String,
+    /// The span of the comment that contains the suppression.
+    span: SourceSpan,
+    /// The byte range of the source in which findings of the lint are suppressed.
+    scope: Range<u32>,
+    is_used: bool,
+}
+
+impl Suppressions {
+    pub fn parse(source_file: Arc<SourceFile>, module: &Module) -> Self {
+        let lines = source_lines(source_file.as_str());
+        let file_scope = 0..source_file.as_str().len() as u32;
+        let mut suppressions = Vec::new();
+        let mut is_top_of_module = true;
+
+        for (line_idx, line) in lines.iter().enumerate() {
+            let Some(lint_names) = parse_suppression(line.text) else {
+                if !line.is_blank() && !line.is_comment() {
+                    is_top_of_module = false;
+                }
+                continue;
+            };
+
+            let following_lines = &lines[line_idx + 1..];
+            let next_code_line =
+                following_lines.iter().find(|line| !line.is_blank() && !line.is_comment());
+            let is_followed_by_blank_line = following_lines
+                .iter()
+                .find(|line| !line.is_comment())
+                .is_some_and(Line::is_blank);
+
+            let scope =
+                if is_top_of_module && (is_followed_by_blank_line || next_code_line.is_none()) {
+                    file_scope.clone()
+                } else if let Some(next_line) = next_code_line {
+                    procedure_scope(module, next_line).unwrap_or(next_line.range())
+                } else {
+                    0..0
+                };
+
+            let text_start = line.start + (line.text.len() - line.text.trim_start().len()) as u32;
+            let span = SourceSpan::new(
+                source_file.id(),
+                text_start..text_start + line.text.trim().len() as u32,
+            );
+
+            for lint_name in lint_names {
+                suppressions.push(Suppression {
+                    lint_name,
+                    span,
+                    scope: scope.clone(),
+                    is_used: false,
+                });
+            }
+        }
+
+        Self { source_file, suppressions }
+    }
+
+    pub fn source_file(&self) -> &Arc<SourceFile> {
+        &self.source_file
+    }
+
+    /// Returns `true` if the error is suppressed and marks the suppressions that apply to it as
+    /// used.
+    pub fn suppress(&mut self, error: &LintErrorKind) -> bool {
+        let start = error.span().start().to_u32();
+        let mut is_suppressed = false;
+
+        for suppression in self.suppressions.iter_mut() {
+            if suppression.lint_name == error.lint_name() && suppression.scope.contains(&start) {
+                suppression.is_used = true;
+                is_suppressed = true;
+            }
+        }
+
+        is_suppressed
+    }
+
+    /// Returns the lint names and comment spans of all suppressions that did not suppress any
+    /// finding.
+    pub fn into_unused(self) -> impl Iterator<Item = (String, SourceSpan)> {
+        self.suppressions
+            .into_iter()
+            .filter(|suppression| !suppression.is_used)
+            .map(|suppression| (suppression.lint_name, suppression.span))
+    }
+}
+
+struct Line<'a> {
+    start: u32,
+    /// The text of the line without the line terminator.
+    text: &'a str,
+}
+
+impl Line<'_> {
+    fn range(&self) -> Range<u32> {
+        self.start..self.start + self.text.len() as u32
+    }
+
+    fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    fn is_comment(&self) -> bool {
+        self.text.trim_start().starts_with('#')
+    }
+}
+
+fn source_lines(source: &str) -> Vec<Line<'_>> {
+    let mut start = 0;
+    source
+        .split_inclusive('\n')
+        .map(|line| {
+            let text = line.trim_end_matches(['\n', '\r']);
+            let parsed_line = Line { start, text };
+            start += line.len() as u32;
+            parsed_line
+        })
+        .collect()
+}
+
+/// Parses the lint names of a suppression comment of the form `# masmlint: allow(a, b)`.
+fn parse_suppression(line: &str) -> Option<Vec<String>> {
+    let comment = line.trim().strip_prefix('#')?.trim_start();
+    let directive = comment.strip_prefix(SUPPRESSION_PREFIX)?.trim();
+    let lint_names = directive.strip_prefix("allow(")?.strip_suffix(')')?;
+
+    Some(
+        lint_names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(String::from)
+            .collect(),
+    )
+}
+
+/// Returns the range of the procedure that is defined on `line`, if any.
+///
+/// The entrypoint of an executable is not a procedure scope, since its span starts at its first
+/// instruction rather than at `begin`.
+fn procedure_scope(module: &Module, line: &Line) -> Option<Range<u32>> {
+    module.procedures().find_map(|export| {
+        if matches!(export, Export::Procedure(procedure) if procedure.is_entrypoint()) {
+            return None;
+        }
+        let span = export.span();
+        line.range().contains(&span.start().to_u32()).then(|| span.into_range())
+    })
+}